@@ -0,0 +1,222 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    instantiate2_address, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdResult, WasmMsg,
+};
+use cw2::set_contract_version;
+use cw_storage_plus::Bound;
+use guild_interfaces::{
+    admin::{assert_owner, OWNER},
+    deposit::DepositReady,
+};
+use ibc_forwarder::msg::{
+    ContractState, InstantiateMsg as ForwarderInstantiateMsg, QueryMsg as ForwarderQueryMsg,
+};
+
+use crate::error::ContractError;
+use crate::msg::{
+    get_forwarder_salt, ConfigResponse, ExecuteMsg, ForwarderResponse, InstantiateMsg, QueryMsg,
+    RemoteChain,
+};
+use crate::state::{FORWARDERS, FORWARDER_CODE_ID, FORWARDER_GUARDIAN, SPLITTER};
+
+const CONTRACT_NAME: &str = "crates.io:forwarder-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    deps.api.debug("WASMDEBUG: instantiate");
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    FORWARDER_CODE_ID.save(deps.storage, &msg.forwarder_code_id)?;
+    let splitter = deps.api.addr_validate(&msg.splitter)?;
+    SPLITTER.save(deps.storage, &splitter)?;
+    if let Some(owner) = msg.owner {
+        OWNER.save(deps.storage, &deps.api.addr_validate(&owner)?)?;
+    }
+    if let Some(guardian) = msg.guardian {
+        FORWARDER_GUARDIAN.save(deps.storage, &deps.api.addr_validate(&guardian)?)?;
+    }
+
+    let instantiate_msgs = get_forwarder_instantiate_msgs(deps, &env, msg.remote_chains)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "forwarder_factory_instantiate")
+        .add_attribute("splitter", splitter)
+        .add_messages(instantiate_msgs))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateForwarders { remote_chains } => {
+            try_create_forwarders(deps, env, info, remote_chains)
+        }
+    }
+}
+
+fn try_create_forwarders(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    remote_chains: Vec<RemoteChain>,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let instantiate_msgs = get_forwarder_instantiate_msgs(deps, &env, remote_chains)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_create_forwarders")
+        .add_messages(instantiate_msgs))
+}
+
+/// registers a forwarder per remote chain under its predicted address and
+/// returns the messages instantiating them
+fn get_forwarder_instantiate_msgs(
+    deps: DepsMut,
+    env: &Env,
+    remote_chains: Vec<RemoteChain>,
+) -> Result<Vec<WasmMsg>, ContractError> {
+    let code_id = FORWARDER_CODE_ID.load(deps.storage)?;
+    let splitter = SPLITTER.load(deps.storage)?;
+    let owner = OWNER.may_load(deps.storage)?;
+    let guardian = FORWARDER_GUARDIAN.may_load(deps.storage)?;
+
+    let mut msgs: Vec<WasmMsg> = vec![];
+    for remote_chain in remote_chains {
+        let salt = get_forwarder_salt(&remote_chain.connection_id, &remote_chain.channel_id);
+        let address = get_forwarder_address(deps.as_ref(), env, &salt)?;
+        if FORWARDERS.has(deps.storage, address.clone()) {
+            return Err(ContractError::ForwarderExists {
+                connection_id: remote_chain.connection_id,
+                channel_id: remote_chain.channel_id,
+            });
+        }
+
+        let instantiate_msg = ForwarderInstantiateMsg {
+            next_contract: splitter.to_string(),
+            remote_chain_connection_id: remote_chain.connection_id.clone(),
+            remote_chain_channel_id: remote_chain.channel_id.clone(),
+            denoms: remote_chain.denoms.clone(),
+            ibc_transfer_timeout: remote_chain.ibc_transfer_timeout,
+            ica_timeout: remote_chain.ica_timeout,
+            icq_update_period: remote_chain.icq_update_period,
+            reserves: remote_chain.reserves.clone(),
+            forwarding_mode: remote_chain.forwarding_mode.clone(),
+            ibc_fee: remote_chain.ibc_fee.clone(),
+            owner: owner.as_ref().map(Addr::to_string),
+            retry_policy: remote_chain.retry_policy.clone(),
+            guardian: guardian.as_ref().map(Addr::to_string),
+            keeper_tip: remote_chain.keeper_tip.clone(),
+            cron_schedule: remote_chain.cron_schedule.clone(),
+        };
+
+        msgs.push(WasmMsg::Instantiate2 {
+            admin: owner.as_ref().map(Addr::to_string),
+            code_id,
+            label: format!("ibc-forwarder {salt}"),
+            msg: to_binary(&instantiate_msg)?,
+            funds: vec![],
+            salt: Binary::from(salt.as_bytes()),
+        });
+
+        FORWARDERS.save(deps.storage, address, &remote_chain)?;
+    }
+
+    Ok(msgs)
+}
+
+/// instantiate2 address of a forwarder, which only depends on the
+/// forwarder code, the factory and the salt
+fn get_forwarder_address(deps: Deps, env: &Env, salt: &str) -> Result<Addr, ContractError> {
+    let code_id = FORWARDER_CODE_ID.load(deps.storage)?;
+    let code_info = deps.querier.query_wasm_code_info(code_id)?;
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+
+    let address = instantiate2_address(code_info.checksum.as_slice(), &creator, salt.as_bytes())?;
+
+    Ok(deps.api.addr_humanize(&address)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Config {} => Ok(to_binary(&ConfigResponse {
+            forwarder_code_id: FORWARDER_CODE_ID.load(deps.storage)?,
+            splitter: SPLITTER.load(deps.storage)?,
+            owner: OWNER.may_load(deps.storage)?,
+            guardian: FORWARDER_GUARDIAN.may_load(deps.storage)?,
+        })?),
+        QueryMsg::Forwarders { start_after, limit } => {
+            Ok(to_binary(&query_forwarders(deps, start_after, limit)?)?)
+        }
+        QueryMsg::ForwarderAddress {
+            connection_id,
+            channel_id,
+        } => {
+            let salt = get_forwarder_salt(&connection_id, &channel_id);
+            Ok(to_binary(&get_forwarder_address(deps, &env, &salt)?)?)
+        }
+    }
+}
+
+pub fn query_forwarders(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ForwarderResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(address) => Some(Bound::exclusive(deps.api.addr_validate(&address)?)),
+        None => None,
+    };
+    let mut forwarders: Vec<ForwarderResponse> = vec![];
+
+    for entry in FORWARDERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+    {
+        let (address, remote_chain) = entry?;
+
+        // forwarders get instantiated after they are registered, so they
+        // may not exist yet. any other query error is passed on.
+        let (contract_state, ica_address) = match deps.querier.query_wasm_contract_info(&address) {
+            Ok(_) => {
+                let contract_state: Option<ContractState> = deps
+                    .querier
+                    .query_wasm_smart(&address, &ForwarderQueryMsg::ContractState {})?;
+                let ica_address = match DepositReady::check(&deps.querier, &address)? {
+                    DepositReady::Ready { address } => Some(address),
+                    DepositReady::NotReady => None,
+                };
+                (contract_state, ica_address)
+            }
+            Err(_) => (None, None),
+        };
+
+        forwarders.push(ForwarderResponse {
+            address,
+            connection_id: remote_chain.connection_id,
+            channel_id: remote_chain.channel_id,
+            denoms: remote_chain.denoms,
+            contract_state,
+            ica_address,
+        });
+    }
+
+    Ok(forwarders)
+}
@@ -0,0 +1,21 @@
+use cosmwasm_std::{Instantiate2AddressError, StdError};
+use guild_interfaces::admin::AdminError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Instantiate2Address(#[from] Instantiate2AddressError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("forwarder for {connection_id}/{channel_id} already exists")]
+    ForwarderExists {
+        connection_id: String,
+        channel_id: String,
+    },
+}
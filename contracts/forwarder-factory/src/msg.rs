@@ -0,0 +1,88 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin, Uint64};
+use ibc_forwarder::msg::{ContractState, ForwardingMode, KeeperTip, RetryPolicy};
+use neutron_sdk::bindings::msg::IbcFee;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// code id of the ibc-forwarder contract
+    pub forwarder_code_id: u64,
+    /// splitter the forwarders send their funds to
+    pub splitter: String,
+    /// remote chains to instantiate forwarders for
+    pub remote_chains: Vec<RemoteChain>,
+    /// address allowed to create more forwarders. also becomes the
+    /// admin and owner of the forwarders.
+    pub owner: Option<String>,
+    /// address allowed to pause and unpause the forwarders besides
+    /// their owner
+    pub guardian: Option<String>,
+}
+
+/// forwarder configuration for a single remote chain.
+/// see the ibc-forwarder `InstantiateMsg` for the meaning of the fields.
+#[cw_serde]
+pub struct RemoteChain {
+    pub connection_id: String,
+    pub channel_id: String,
+    pub denoms: Vec<String>,
+    pub ibc_transfer_timeout: Uint64,
+    pub ica_timeout: Uint64,
+    pub icq_update_period: Uint64,
+    pub reserves: Option<Vec<Coin>>,
+    pub forwarding_mode: Option<ForwardingMode>,
+    pub ibc_fee: Option<IbcFee>,
+    pub retry_policy: Option<RetryPolicy>,
+    pub keeper_tip: Option<KeeperTip>,
+    pub cron_schedule: Option<String>,
+}
+
+/// instantiate2 salt of a forwarder. there is at most one forwarder
+/// per connection and channel.
+pub fn get_forwarder_salt(connection_id: &str, channel_id: &str) -> String {
+    format!("{connection_id}/{channel_id}")
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// instantiates forwarders for more remote chains. owner only.
+    CreateForwarders { remote_chains: Vec<RemoteChain> },
+}
+
+#[derive(QueryResponses)]
+#[cw_serde]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// forwarders along with their current state, ordered by address
+    #[returns(Vec<ForwarderResponse>)]
+    Forwarders {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// address the forwarder for the remote chain has or will have
+    #[returns(Addr)]
+    ForwarderAddress {
+        connection_id: String,
+        channel_id: String,
+    },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub forwarder_code_id: u64,
+    pub splitter: Addr,
+    pub owner: Option<Addr>,
+    pub guardian: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct ForwarderResponse {
+    pub address: Addr,
+    pub connection_id: String,
+    pub channel_id: String,
+    pub denoms: Vec<String>,
+    pub contract_state: Option<ContractState>,
+    /// ICA of the forwarder on the remote chain, once it is opened
+    pub ica_address: Option<String>,
+}
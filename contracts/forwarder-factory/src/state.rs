@@ -0,0 +1,16 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+
+use crate::msg::RemoteChain;
+
+/// code id of the ibc-forwarder contract to instantiate
+pub const FORWARDER_CODE_ID: Item<u64> = Item::new("forwarder_code_id");
+
+/// splitter that receives the funds of all forwarders
+pub const SPLITTER: Item<Addr> = Item::new("splitter");
+
+/// guardian of the forwarders
+pub const FORWARDER_GUARDIAN: Item<Addr> = Item::new("forwarder_guardian");
+
+/// forwarder address -> remote chain it forwards from
+pub const FORWARDERS: Map<Addr, RemoteChain> = Map::new("forwarders");
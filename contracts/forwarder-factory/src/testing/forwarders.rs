@@ -0,0 +1,160 @@
+use cosmwasm_std::{from_binary, WasmMsg};
+use ibc_forwarder::msg::{ContractState, InstantiateMsg as ForwarderInstantiateMsg};
+
+use super::{instantiate2_msgs, remote_chain, Suite, CONNECTION_ID, FORWARDER_CODE_ID};
+
+#[test]
+fn forwarder_address_is_derived_from_the_salt() {
+    let mut suite = Suite::new(vec![]);
+    let owner = suite.owner.clone();
+
+    let response = suite
+        .create_forwarders(&owner, vec![remote_chain("channel-1")])
+        .unwrap();
+
+    let salt = format!("{CONNECTION_ID}/channel-1");
+    let expected = suite.predicted_address(&salt);
+    let msgs = instantiate2_msgs(&response);
+    assert_eq!(msgs.len(), 1);
+    let WasmMsg::Instantiate2 {
+        admin,
+        code_id,
+        msg,
+        ..
+    } = &msgs[0].0
+    else {
+        unreachable!()
+    };
+    assert_eq!(msgs[0].1, salt);
+    assert_eq!(*code_id, FORWARDER_CODE_ID);
+    assert_eq!(admin.as_deref(), Some(owner.as_str()));
+    let msg: ForwarderInstantiateMsg = from_binary(msg).unwrap();
+    assert_eq!(msg.remote_chain_channel_id, "channel-1");
+    assert_eq!(msg.owner.as_deref(), Some(owner.as_str()));
+    let guardian = suite.deps.api.addr_make("guardian");
+    assert_eq!(msg.guardian.as_deref(), Some(guardian.as_str()));
+    assert_eq!(suite.query_forwarder_address("channel-1"), expected);
+
+    // registered forwarders are listed before they get instantiated
+    let forwarders = suite.query_forwarders(None, None);
+    assert_eq!(forwarders.len(), 1);
+    assert_eq!(forwarders[0].address, expected);
+    assert_eq!(forwarders[0].contract_state, None);
+
+    suite.set_instantiated_forwarders(vec![expected.clone()]);
+    let forwarders = suite.query_forwarders(None, None);
+    assert_eq!(forwarders.len(), 1);
+    assert_eq!(forwarders[0].address, expected);
+    assert_eq!(forwarders[0].channel_id, "channel-1");
+    assert_eq!(
+        forwarders[0].contract_state,
+        Some(ContractState::Instantiated)
+    );
+    assert_eq!(forwarders[0].ica_address, None);
+
+    // the address of a forwarder that does not exist yet is known too
+    let other = suite.query_forwarder_address("channel-2");
+    assert_eq!(
+        other,
+        suite.predicted_address(&format!("{CONNECTION_ID}/channel-2"))
+    );
+    assert_ne!(other, expected);
+}
+
+#[test]
+fn forwarders_from_instantiate_use_the_same_addresses() {
+    let suite = Suite::new(vec![remote_chain("channel-1"), remote_chain("channel-2")]);
+
+    let salts: Vec<_> = instantiate2_msgs(&suite.response)
+        .into_iter()
+        .map(|(_, salt)| salt)
+        .collect();
+    assert_eq!(
+        salts,
+        vec![
+            format!("{CONNECTION_ID}/channel-1"),
+            format!("{CONNECTION_ID}/channel-2"),
+        ]
+    );
+
+    let mut addresses: Vec<_> = suite
+        .query_forwarders(None, None)
+        .into_iter()
+        .map(|forwarder| forwarder.address)
+        .collect();
+    addresses.sort();
+
+    let mut expected = vec![
+        suite.predicted_address(&format!("{CONNECTION_ID}/channel-1")),
+        suite.predicted_address(&format!("{CONNECTION_ID}/channel-2")),
+    ];
+    expected.sort();
+    assert_eq!(addresses, expected);
+}
+
+#[test]
+fn rejects_a_second_forwarder_for_the_same_channel() {
+    let mut suite = Suite::new(vec![remote_chain("channel-1")]);
+    let owner = suite.owner.clone();
+
+    let err = suite
+        .create_forwarders(&owner, vec![remote_chain("channel-1")])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("forwarder for connection-0/channel-1 already exists"));
+
+    // a duplicate in the same batch is caught as well
+    let err = suite
+        .create_forwarders(
+            &owner,
+            vec![remote_chain("channel-2"), remote_chain("channel-2")],
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("forwarder for connection-0/channel-2 already exists"));
+}
+
+#[test]
+fn only_the_owner_creates_forwarders() {
+    let mut suite = Suite::new(vec![]);
+    let stranger = suite.deps.api.addr_make("stranger");
+
+    suite
+        .create_forwarders(&stranger, vec![remote_chain("channel-1")])
+        .unwrap_err();
+    assert!(suite.query_forwarders(None, None).is_empty());
+}
+
+#[test]
+fn forwarders_are_paginated() {
+    let suite = Suite::new(vec![
+        remote_chain("channel-1"),
+        remote_chain("channel-2"),
+        remote_chain("channel-3"),
+    ]);
+
+    let all = suite.query_forwarders(None, None);
+    assert_eq!(all.len(), 3);
+    let addresses: Vec<_> = all
+        .iter()
+        .map(|forwarder| forwarder.address.clone())
+        .collect();
+    let mut sorted = addresses.clone();
+    sorted.sort();
+    assert_eq!(addresses, sorted);
+
+    let first_page = suite.query_forwarders(None, Some(2));
+    assert_eq!(first_page, all[..2]);
+
+    let second_page = suite.query_forwarders(Some(&first_page[1].address), Some(2));
+    assert_eq!(second_page, all[2..]);
+
+    let after_first = suite.query_forwarders(Some(&all[0].address), None);
+    assert_eq!(after_first, all[1..]);
+
+    assert!(suite
+        .query_forwarders(Some(&all[2].address), None)
+        .is_empty());
+}
@@ -0,0 +1,319 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use bech32::{FromBase32, ToBase32, Variant};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_binary, instantiate2_address,
+    testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+    to_binary, Addr, Api, CanonicalAddr, ContractResult, CosmosMsg, Env, HexBinary, OwnedDeps,
+    RecoverPubkeyError, Response, StdError, StdResult, SystemError, SystemResult, Uint64,
+    VerificationError, WasmMsg, WasmQuery,
+};
+use guild_interfaces::deposit::DepositAddressResponse;
+use ibc_forwarder::msg::{ContractState, QueryMsg as ForwarderQueryMsg};
+use std::marker::PhantomData;
+
+use crate::{
+    contract::{execute, instantiate, query},
+    error::ContractError,
+    msg::{ExecuteMsg, ForwarderResponse, InstantiateMsg, QueryMsg, RemoteChain},
+};
+
+mod forwarders;
+
+const PREFIX: &str = "neutron";
+const CONNECTION_ID: &str = "connection-0";
+const DENOM: &str = "uatom";
+const FORWARDER_CODE_ID: u64 = 7;
+const FORWARDER_CHECKSUM: [u8; 32] = [7; 32];
+
+/// the mock api of cosmwasm 1.2 can not humanize the 32 byte addresses
+/// derived by instantiate2, so bech32 addresses are used instead
+#[derive(Clone, Copy, Default)]
+pub struct Bech32Api {
+    mock: MockApi,
+}
+
+impl Bech32Api {
+    /// bech32 address made out of `name`
+    pub fn addr_make(&self, name: &str) -> Addr {
+        self.addr_humanize(&CanonicalAddr::from(name.as_bytes()))
+            .unwrap()
+    }
+}
+
+impl Api for Bech32Api {
+    fn addr_validate(&self, input: &str) -> StdResult<Addr> {
+        let canonical = self.addr_canonicalize(input)?;
+        let normalized = self.addr_humanize(&canonical)?;
+        if normalized != input {
+            return Err(StdError::generic_err("address not normalized"));
+        }
+        Ok(normalized)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        let (prefix, data, _) =
+            bech32::decode(human).map_err(|err| StdError::generic_err(err.to_string()))?;
+        if prefix != PREFIX {
+            return Err(StdError::generic_err("wrong address prefix"));
+        }
+        let bytes =
+            Vec::<u8>::from_base32(&data).map_err(|err| StdError::generic_err(err.to_string()))?;
+        Ok(bytes.into())
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        bech32::encode(PREFIX, canonical.as_slice().to_base32(), Variant::Bech32)
+            .map(Addr::unchecked)
+            .map_err(|err| StdError::generic_err(err.to_string()))
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.mock
+            .secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.mock
+            .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.mock.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.mock
+            .ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.mock.debug(message)
+    }
+}
+
+/// `CodeInfoResponse` can not be built outside of cosmwasm-std
+#[cw_serde]
+struct CodeInfo {
+    code_id: u64,
+    creator: String,
+    checksum: HexBinary,
+}
+
+/// `ContractInfoResponse` can not be built outside of cosmwasm-std
+#[cw_serde]
+struct ContractInfo {
+    code_id: u64,
+    creator: String,
+    admin: Option<String>,
+    pinned: bool,
+    ibc_port: Option<String>,
+}
+
+pub fn remote_chain(channel_id: &str) -> RemoteChain {
+    RemoteChain {
+        connection_id: CONNECTION_ID.to_string(),
+        channel_id: channel_id.to_string(),
+        denoms: vec![DENOM.to_string()],
+        ibc_transfer_timeout: Uint64::new(100),
+        ica_timeout: Uint64::new(100),
+        icq_update_period: Uint64::new(10),
+        reserves: None,
+        forwarding_mode: None,
+        ibc_fee: None,
+        retry_policy: None,
+        keeper_tip: None,
+        cron_schedule: None,
+    }
+}
+
+pub struct Suite {
+    pub deps: OwnedDeps<MockStorage, Bech32Api, MockQuerier>,
+    pub env: Env,
+    pub owner: Addr,
+    /// response of the factory instantiation
+    pub response: Response,
+}
+
+impl Suite {
+    pub fn new(remote_chains: Vec<RemoteChain>) -> Self {
+        let api = Bech32Api::default();
+        let owner = api.addr_make("owner");
+        let splitter = api.addr_make("splitter");
+        let mut env = mock_env();
+        env.contract.address = api.addr_make("factory");
+
+        let mut suite = Self {
+            deps: OwnedDeps {
+                storage: MockStorage::new(),
+                api,
+                querier: MockQuerier::new(&[]),
+                custom_query_type: PhantomData,
+            },
+            env,
+            owner: owner.clone(),
+            response: Response::default(),
+        };
+        suite.set_instantiated_forwarders(vec![]);
+
+        suite.response = instantiate(
+            suite.deps.as_mut(),
+            suite.env.clone(),
+            mock_info(owner.as_str(), &[]),
+            InstantiateMsg {
+                forwarder_code_id: FORWARDER_CODE_ID,
+                splitter: splitter.to_string(),
+                remote_chains,
+                owner: Some(owner.to_string()),
+                guardian: Some(api.addr_make("guardian").to_string()),
+            },
+        )
+        .unwrap();
+
+        suite
+    }
+
+    /// forwarders that answer queries. the factory only needs them to
+    /// answer the state and deposit queries, so stubs stand in for the
+    /// neutron-bound forwarders.
+    pub fn set_instantiated_forwarders(&mut self, forwarders: Vec<Addr>) {
+        let creator = self.env.contract.address.to_string();
+        self.deps.querier.update_wasm(move |query| match query {
+            WasmQuery::CodeInfo { code_id } if *code_id == FORWARDER_CODE_ID => {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&CodeInfo {
+                        code_id: *code_id,
+                        creator: creator.to_string(),
+                        checksum: HexBinary::from(FORWARDER_CHECKSUM.to_vec()),
+                    })
+                    .unwrap(),
+                ))
+            }
+            WasmQuery::ContractInfo { contract_addr }
+                if forwarders.iter().any(|addr| addr.as_str() == contract_addr) =>
+            {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&ContractInfo {
+                        code_id: FORWARDER_CODE_ID,
+                        creator: creator.to_string(),
+                        admin: None,
+                        pinned: false,
+                        ibc_port: None,
+                    })
+                    .unwrap(),
+                ))
+            }
+            WasmQuery::Smart { contract_addr, msg }
+                if forwarders.iter().any(|addr| addr.as_str() == contract_addr) =>
+            {
+                let response = match from_binary(msg).unwrap() {
+                    ForwarderQueryMsg::ContractState {} => to_binary(&ContractState::Instantiated),
+                    ForwarderQueryMsg::DepositAddress {} => {
+                        let response: DepositAddressResponse = None;
+                        to_binary(&response)
+                    }
+                    _ => Err(StdError::generic_err("unsupported forwarder query")),
+                };
+                SystemResult::Ok(response.into())
+            }
+            WasmQuery::ContractInfo { contract_addr } | WasmQuery::Smart { contract_addr, .. } => {
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.to_string(),
+                })
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_string(),
+            }),
+        });
+    }
+
+    pub fn create_forwarders(
+        &mut self,
+        sender: &Addr,
+        remote_chains: Vec<RemoteChain>,
+    ) -> Result<Response, ContractError> {
+        execute(
+            self.deps.as_mut(),
+            self.env.clone(),
+            mock_info(sender.as_str(), &[]),
+            ExecuteMsg::CreateForwarders { remote_chains },
+        )
+    }
+
+    pub fn query_forwarder_address(&self, channel_id: &str) -> Addr {
+        let response = query(
+            self.deps.as_ref(),
+            self.env.clone(),
+            QueryMsg::ForwarderAddress {
+                connection_id: CONNECTION_ID.to_string(),
+                channel_id: channel_id.to_string(),
+            },
+        )
+        .unwrap();
+        from_binary(&response).unwrap()
+    }
+
+    pub fn query_forwarders(
+        &self,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
+    ) -> Vec<ForwarderResponse> {
+        let response = query(
+            self.deps.as_ref(),
+            self.env.clone(),
+            QueryMsg::Forwarders {
+                start_after: start_after.map(Addr::to_string),
+                limit,
+            },
+        )
+        .unwrap();
+        from_binary(&response).unwrap()
+    }
+
+    /// instantiate2 address of the forwarder for `salt`, computed outside of
+    /// the factory
+    pub fn predicted_address(&self, salt: &str) -> Addr {
+        let creator = self
+            .deps
+            .api
+            .addr_canonicalize(self.env.contract.address.as_str())
+            .unwrap();
+        let address = instantiate2_address(&FORWARDER_CHECKSUM, &creator, salt.as_bytes()).unwrap();
+        self.deps.api.addr_humanize(&address).unwrap()
+    }
+}
+
+/// instantiate2 messages of a response, along with their salts
+pub fn instantiate2_msgs(response: &Response) -> Vec<(WasmMsg, String)> {
+    response
+        .messages
+        .iter()
+        .filter_map(|submsg| match &submsg.msg {
+            CosmosMsg::Wasm(msg @ WasmMsg::Instantiate2 { salt, .. }) => {
+                Some((msg.clone(), String::from_utf8(salt.to_vec()).unwrap()))
+            }
+            _ => None,
+        })
+        .collect()
+}
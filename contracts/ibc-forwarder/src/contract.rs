@@ -1,16 +1,38 @@
-use cosmos_sdk_proto::ibc::applications::transfer::v1::MsgTransfer;
+use cosmos_sdk_proto::{
+    cosmos::bank::v1beta1::MsgSend, ibc::applications::transfer::v1::MsgTransfer,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, to_vec, Binary, CosmosMsg, CustomQuery, Deps, DepsMut, Env,
-    MessageInfo, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128,
+    to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, CustomQuery, Deps, DepsMut, Env,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Timestamp, Uint128,
 };
 use cw2::set_contract_version;
+use cw_storage_plus::{Bound, Map};
+use guild_interfaces::{
+    admin::{
+        assert_migration, assert_not_paused, assert_owner, is_paused, query_ownership,
+        try_accept_ownership, try_set_paused, try_transfer_ownership, try_update_guardian,
+        GUARDIAN, OWNER,
+    },
+    cron::{ContractSudoMsg, CronSudoMsg},
+    deposit::DepositReady,
+    fee::{ibc_fee_or_min, validate_ibc_fee},
+    keeper::{is_tip_due, LAST_PAID_TICK},
+};
 use neutron_sdk::{
     bindings::{
-        msg::{IbcFee, MsgSubmitTxResponse, NeutronMsg},
+        msg::{IbcFee, MsgRegisterInterchainQueryResponse, MsgSubmitTxResponse, NeutronMsg},
         query::NeutronQuery,
-        types::ProtobufAny,
+        types::{KVKey, ProtobufAny},
+    },
+    interchain_queries::{
+        types::QueryPayload,
+        v045::{
+            helpers::{create_account_denom_balance_key, decode_and_convert},
+            queries::query_balance,
+            types::BANK_STORE_KEY,
+        },
     },
     interchain_txs::helpers::get_port_id,
     sudo::msg::{RequestPacket, SudoMsg},
@@ -19,20 +41,33 @@ use neutron_sdk::{
 use prost::Message;
 
 use crate::{
+    error::ContractError,
     msg::{
-        ContractState, ExecuteMsg, InstantiateMsg, OpenAckVersion, QueryMsg, RemoteChainInfo,
-        SudoPayload,
+        ChannelHealth, ContractState, ExecuteMsg, FailedRequest, FeeSpendResponse, ForwardingMode,
+        InstantiateMsg, KeeperTip, KeeperTipResponse, MigrateMsg, OpenAckVersion, PendingRequest,
+        QueryMsg, RemoteBalanceResponse, RemoteChainInfo, RetryPolicy, RetryStatus, SudoPayload,
     },
     state::{
-        CONTRACT_STATE, INTERCHAIN_ACCOUNTS, NEXT_CONTRACT, REMOTE_CHAIN_INFO, REPLY_ID_STORAGE,
-        SUDO_PAYLOAD,
+        BALANCE_QUERY_ID, CHANNEL_HEALTH, CONSECUTIVE_FAILURES, CONTRACT_STATE, CRON_SCHEDULE,
+        FAILED_REQUESTS, FEES_PAID, FEES_REFUNDED, FORWARDED_TOTALS, FORWARDING_MODE,
+        ICA_REGISTRATION_SENT_AT, INTERCHAIN_ACCOUNTS, KEEPER_TIP, LAST_FORWARDED_HEIGHT,
+        LEGACY_REMOTE_CHAIN_INFO, LEGACY_REPLY_ID_STORAGE, LEGACY_SUDO_PAYLOAD, NEXT_ATTEMPT,
+        NEXT_CONTRACT, NEXT_REPLY_ID, PENDING_REQUESTS, REMOTE_BALANCE, REMOTE_CHAIN_INFO,
+        REPLY_PAYLOADS, RETRY_POLICY,
     },
 };
 
 const CONTRACT_NAME: &str = "crates.io:ibc-forwarder";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 const INTERCHAIN_ACCOUNT_ID: &str = "ica";
-pub const SUDO_PAYLOAD_REPLY_ID: u64 = 1;
+pub const BALANCE_QUERY_REPLY_ID: u64 = 2;
+/// reply ids of ICA tx submissions are allocated from here upwards
+pub const SUDO_PAYLOAD_REPLY_ID_START: u64 = 1000;
+const FORWARD_FUNDS_PAYLOAD: &str = "try_forward_funds";
+const EMERGENCY_WITHDRAW_PAYLOAD: &str = "try_emergency_withdraw";
+const KEEPER_TIP_DENOM: &str = "untrn";
+const DEFAULT_QUERY_LIMIT: u32 = 10;
+const MAX_QUERY_LIMIT: u32 = 30;
 
 type QueryDeps<'a> = Deps<'a, NeutronQuery>;
 type ExecuteDeps<'a> = DepsMut<'a, NeutronQuery>;
@@ -49,28 +84,51 @@ pub fn instantiate(
     let next_contract = deps.api.addr_validate(&msg.next_contract)?;
     NEXT_CONTRACT.save(deps.storage, &next_contract)?;
 
+    if msg.denoms.is_empty() {
+        return Err(NeutronError::Std(StdError::generic_err(
+            "at least one denom must be forwarded",
+        )));
+    }
+
+    let ibc_fee = ibc_fee_or_min(deps.as_ref(), msg.ibc_fee)?;
+
     let remote_chain_info = RemoteChainInfo {
         connection_id: msg.remote_chain_connection_id,
         channel_id: msg.remote_chain_channel_id,
-        denom: msg.denom,
-        ibc_fee: IbcFee {
-            // must be empty
-            recv_fee: vec![],
-            ack_fee: vec![cosmwasm_std::Coin {
-                denom: "untrn".to_string(),
-                amount: Uint128::new(10000),
-            }],
-            timeout_fee: vec![cosmwasm_std::Coin {
-                denom: "untrn".to_string(),
-                amount: Uint128::new(10000),
-            }],
-        },
+        denoms: msg.denoms,
+        ibc_fee,
         ica_timeout: msg.ica_timeout,
         ibc_transfer_timeout: msg.ibc_transfer_timeout,
+        icq_update_period: msg.icq_update_period,
+        reserves: msg.reserves.unwrap_or_default(),
     };
     REMOTE_CHAIN_INFO.save(deps.storage, &remote_chain_info)?;
+    FORWARDING_MODE.save(
+        deps.storage,
+        &msg.forwarding_mode.unwrap_or(ForwardingMode::Recurring),
+    )?;
     CONTRACT_STATE.save(deps.storage, &ContractState::Instantiated)?;
 
+    if let Some(owner) = msg.owner {
+        OWNER.save(deps.storage, &deps.api.addr_validate(&owner)?)?;
+    }
+
+    let retry_policy = validate_retry_policy(msg.retry_policy.unwrap_or_default())?;
+    RETRY_POLICY.save(deps.storage, &retry_policy)?;
+
+    if let Some(guardian) = msg.guardian {
+        GUARDIAN.save(deps.storage, &deps.api.addr_validate(&guardian)?)?;
+    }
+
+    if let Some(keeper_tip) = msg.keeper_tip {
+        keeper_tip.validate()?;
+        KEEPER_TIP.save(deps.storage, &keeper_tip)?;
+    }
+
+    if let Some(schedule) = msg.cron_schedule {
+        CRON_SCHEDULE.save(deps.storage, &schedule)?;
+    }
+
     Ok(Response::default()
         .add_attribute("method", "ibc_forwarder_instantiate")
         .add_attribute("next_contract", next_contract)
@@ -85,24 +143,345 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> NeutronResult<Response<NeutronMsg>> {
     match msg {
-        ExecuteMsg::Tick {} => try_tick(deps, env, info),
+        ExecuteMsg::Tick {} => try_tick(deps, env, Some(info.sender)),
+        ExecuteMsg::UpdateIbcFee { ibc_fee } => try_update_ibc_fee(deps, info, ibc_fee),
+        ExecuteMsg::TransferOwnership { new_owner } => {
+            Ok(try_transfer_ownership(deps, info, new_owner)?)
+        }
+        ExecuteMsg::AcceptOwnership {} => Ok(try_accept_ownership(deps, info)?),
+        ExecuteMsg::UpdateRetryPolicy { retry_policy } => {
+            try_update_retry_policy(deps, info, retry_policy)
+        }
+        ExecuteMsg::Pause {} => Ok(try_set_paused(deps, info, true)?),
+        ExecuteMsg::Unpause {} => Ok(try_set_paused(deps, info, false)?),
+        ExecuteMsg::UpdateGuardian { guardian } => Ok(try_update_guardian(deps, info, guardian)?),
+        ExecuteMsg::EmergencyWithdraw { recovery_address } => {
+            try_emergency_withdraw(deps, env, info, recovery_address)
+        }
+        ExecuteMsg::UpdateKeeperTip { keeper_tip } => try_update_keeper_tip(deps, info, keeper_tip),
+        ExecuteMsg::UpdateCronSchedule { schedule } => {
+            try_update_cron_schedule(deps, info, schedule)
+        }
     }
 }
 
-/// attempts to advance the state machine
-fn try_tick(
+fn try_update_keeper_tip(
     deps: ExecuteDeps,
+    info: MessageInfo,
+    keeper_tip: Option<KeeperTip>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match keeper_tip {
+        Some(keeper_tip) => {
+            keeper_tip.validate()?;
+            KEEPER_TIP.save(deps.storage, &keeper_tip)?
+        }
+        None => KEEPER_TIP.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_keeper_tip"))
+}
+
+fn try_update_cron_schedule(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    schedule: Option<String>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match schedule {
+        Some(schedule) => CRON_SCHEDULE.save(deps.storage, &schedule)?,
+        None => CRON_SCHEDULE.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_cron_schedule"))
+}
+
+/// sends the whole ICA balance of the forwarded denoms, reserves
+/// included, to the recovery address on the remote chain
+fn try_emergency_withdraw(
+    mut deps: ExecuteDeps,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
+    recovery_address: String,
 ) -> NeutronResult<Response<NeutronMsg>> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    // a pending forward is based on the same balances, so the ICA
+    // could not cover both of them
+    if CONTRACT_STATE.load(deps.storage)? == ContractState::ForwardPending {
+        return Err(ContractError::ForwardPending {}.into());
+    }
+
+    let (ica_address, controller_conn_id) = get_ica(deps.as_ref(), &env, INTERCHAIN_ACCOUNT_ID)?;
+    validate_recovery_address(&recovery_address, &ica_address)?;
+    let Some(query_id) = BALANCE_QUERY_ID.may_load(deps.storage)? else {
+        return Err(NeutronError::Std(StdError::generic_err(
+            "ICA balance is not known yet",
+        )));
+    };
+
+    let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
+    let balance_response = query_balance(deps.as_ref(), env.clone(), query_id)?;
+    let withdraw_coins: Vec<Coin> = balance_response
+        .balances
+        .coins
+        .into_iter()
+        .filter(|c| remote_chain_info.denoms.contains(&c.denom) && !c.amount.is_zero())
+        .collect();
+    if withdraw_coins.is_empty() {
+        return Err(NeutronError::Std(StdError::generic_err(
+            "ICA holds nothing to withdraw",
+        )));
+    }
+    // the withdrawn balances must not be forwarded afterwards
+    LAST_FORWARDED_HEIGHT.save(deps.storage, &balance_response.last_submitted_local_height)?;
+
+    let send_msg = MsgSend {
+        from_address: ica_address,
+        to_address: recovery_address.to_string(),
+        amount: withdraw_coins
+            .iter()
+            .map(|coin| cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                denom: coin.denom.to_string(),
+                amount: coin.amount.to_string(),
+            })
+            .collect(),
+    };
+
+    assert_fee_balance(deps.as_ref(), &env, &remote_chain_info.ibc_fee)?;
+
+    let submit_msg = NeutronMsg::submit_tx(
+        controller_conn_id,
+        INTERCHAIN_ACCOUNT_ID.to_string(),
+        vec![to_proto_any(send_msg, "/cosmos.bank.v1beta1.MsgSend")?],
+        "".to_string(),
+        remote_chain_info.ica_timeout.u64(),
+        remote_chain_info.ibc_fee.clone(),
+    );
+
+    let submsg = msg_with_sudo_callback(
+        deps.branch(),
+        submit_msg,
+        SudoPayload {
+            port_id: get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID),
+            message: EMERGENCY_WITHDRAW_PAYLOAD.to_string(),
+            coins: withdraw_coins,
+            ibc_fee: Some(remote_chain_info.ibc_fee),
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_emergency_withdraw")
+        .add_attribute("recovery_address", recovery_address)
+        .add_submessage(submsg))
+}
+
+/// the recovery address lives on the remote chain, so it has to be a
+/// bech32 address with the prefix of the ICA
+fn validate_recovery_address(recovery_address: &str, ica_address: &str) -> NeutronResult<()> {
+    let ica_prefix = bech32::decode(ica_address)
+        .map(|(prefix, _, _)| prefix)
+        .map_err(|e| NeutronError::Std(StdError::generic_err(e.to_string())))?;
+
+    match bech32::decode(recovery_address) {
+        Ok((prefix, data, bech32::Variant::Bech32)) if prefix == ica_prefix && !data.is_empty() => {
+            Ok(())
+        }
+        _ => Err(ContractError::InvalidRecoveryAddress {}.into()),
+    }
+}
+
+fn try_update_ibc_fee(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    ibc_fee: IbcFee,
+) -> NeutronResult<Response<NeutronMsg>> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let ibc_fee = validate_ibc_fee(deps.as_ref(), ibc_fee)?;
+    REMOTE_CHAIN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+        info.ibc_fee = ibc_fee;
+        Ok(info)
+    })?;
+
+    Ok(Response::default().add_attribute("method", "try_update_ibc_fee"))
+}
+
+fn try_update_retry_policy(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    retry_policy: RetryPolicy,
+) -> NeutronResult<Response<NeutronMsg>> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let retry_policy = validate_retry_policy(retry_policy)?;
+    RETRY_POLICY.save(deps.storage, &retry_policy)?;
+
+    Ok(Response::default().add_attribute("method", "try_update_retry_policy"))
+}
+
+fn validate_retry_policy(retry_policy: RetryPolicy) -> NeutronResult<RetryPolicy> {
+    if retry_policy.base_delay.is_zero() || retry_policy.max_delay < retry_policy.base_delay {
+        return Err(NeutronError::Std(StdError::generic_err(
+            "retry base_delay must be positive and not exceed max_delay",
+        )));
+    }
+    Ok(retry_policy)
+}
+
+/// both the ack and the timeout fee are escrowed on submission, so the
+/// contract has to hold enough for both of them
+fn assert_fee_balance(deps: QueryDeps, env: &Env, ibc_fee: &IbcFee) -> NeutronResult<()> {
+    let mut required: Vec<Coin> = vec![];
+    for coin in ibc_fee.ack_fee.iter().chain(ibc_fee.timeout_fee.iter()) {
+        match required.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(c) => c.amount += coin.amount,
+            None => required.push(coin.clone()),
+        }
+    }
+
+    for coin in required {
+        let balance = deps
+            .querier
+            .query_balance(&env.contract.address, &coin.denom)?;
+        if balance.amount < coin.amount {
+            return Err(ContractError::InsufficientFeeBalance {}.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// attempts to advance the state machine. the keeper, if any, gets tipped.
+fn try_tick(
+    mut deps: ExecuteDeps,
+    env: Env,
+    keeper: Option<Addr>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    assert_not_paused(deps.storage)?;
+
+    // only ticks that advance the forwarder get tipped
     let current_state = CONTRACT_STATE.load(deps.storage)?;
-    match current_state {
-        ContractState::Instantiated => try_register_ica(deps, env),
-        ContractState::IcaCreated => try_forward_funds(env, deps),
+
+    // the ICA handshake spans several blocks. a registration in flight is
+    // not re-sent until its open ack arrives or `ica_timeout` has passed.
+    if matches!(
+        current_state,
+        ContractState::Instantiated | ContractState::IcaRecovering
+    ) {
+        if let Some(sent_at) = ICA_REGISTRATION_SENT_AT.may_load(deps.storage)? {
+            let ica_timeout = REMOTE_CHAIN_INFO.load(deps.storage)?.ica_timeout;
+            let expires_at = sent_at.plus_seconds(ica_timeout.u64());
+            if env.block.time < expires_at {
+                return Ok(Response::default()
+                    .add_attribute("contract_state", "ica_registration_pending")
+                    .add_attribute("expires_at", expires_at.to_string()));
+            }
+        }
+    }
+
+    let response = match current_state {
+        ContractState::Instantiated => try_register_ica(deps.branch(), env.clone())?,
+        ContractState::IcaCreated => {
+            // after failed transfers we back off before forwarding again
+            match NEXT_ATTEMPT.may_load(deps.storage)? {
+                Some(next_attempt) if env.block.time < next_attempt => {
+                    return Ok(Response::default()
+                        .add_attribute("contract_state", "retry_backoff")
+                        .add_attribute("next_attempt", next_attempt.to_string()))
+                }
+                _ => try_forward_funds(env.clone(), deps.branch())?,
+            }
+        }
+        // the sudo callback of the pending transfer advances the state
+        ContractState::ForwardPending => {
+            return Ok(Response::default().add_attribute("contract_state", "forward_pending"))
+        }
         ContractState::Complete => {
-            Ok(Response::default().add_attribute("contract_state", "completed"))
+            return Ok(Response::default().add_attribute("contract_state", "completed"))
         }
+        ContractState::IcaRecovering => try_recover_ica(deps.branch(), &env)?,
+    };
+
+    match keeper {
+        Some(keeper) => pay_keeper_tip(deps, &env, keeper, response),
+        None => Ok(response),
+    }
+}
+
+/// adds the keeper tip to the response of a tick, unless the last paid
+/// tick happened less than `min_interval` seconds ago or the tip would
+/// eat into the untrn needed for IBC fees
+fn pay_keeper_tip(
+    deps: ExecuteDeps,
+    env: &Env,
+    keeper: Addr,
+    response: Response<NeutronMsg>,
+) -> NeutronResult<Response<NeutronMsg>> {
+    let Some(keeper_tip) = KEEPER_TIP.may_load(deps.storage)? else {
+        return Ok(response);
+    };
+    if keeper_tip.amount.is_zero() {
+        return Ok(response);
+    }
+    if !is_tip_due(deps.storage, env, keeper_tip.min_interval)? {
+        return Ok(response);
+    }
+
+    // we keep enough for the fees of the tx submitted by this tick
+    // and of the one after it
+    let ibc_fee = REMOTE_CHAIN_INFO.load(deps.storage)?.ibc_fee;
+    let fee_amount = ibc_fee
+        .ack_fee
+        .iter()
+        .chain(ibc_fee.timeout_fee.iter())
+        .filter(|coin| coin.denom == KEEPER_TIP_DENOM)
+        .try_fold(Uint128::zero(), |total, coin| {
+            total.checked_add(coin.amount)
+        })
+        .map_err(StdError::from)?;
+    let required = fee_amount
+        .checked_add(fee_amount)
+        .and_then(|fees| fees.checked_add(keeper_tip.amount))
+        .map_err(|_| ContractError::InvalidKeeperTip {})?;
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, KEEPER_TIP_DENOM)?;
+    if balance.amount < required {
+        return Ok(response.add_attribute("keeper_tip", "insufficient_balance"));
     }
+
+    LAST_PAID_TICK.save(deps.storage, &env.block.time)?;
+
+    Ok(response
+        .add_attribute("keeper_tip", keeper_tip.amount.to_string())
+        .add_message(BankMsg::Send {
+            to_address: keeper.to_string(),
+            amount: vec![Coin {
+                denom: KEEPER_TIP_DENOM.to_string(),
+                amount: keeper_tip.amount,
+            }],
+        }))
+}
+
+/// re-registers the ICA after its channel got closed by a timeout.
+/// registering on the same port and connection reopens the channel for
+/// the same ICA address, so the stored address is kept and keeps being
+/// served as the deposit address in the meantime.
+fn try_recover_ica(deps: ExecuteDeps, env: &Env) -> NeutronResult<Response<NeutronMsg>> {
+    let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
+
+    let register_msg = NeutronMsg::register_interchain_account(
+        remote_chain_info.connection_id,
+        INTERCHAIN_ACCOUNT_ID.to_string(),
+    );
+    ICA_REGISTRATION_SENT_AT.save(deps.storage, &env.block.time)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "try_recover_ica")
+        .add_message(register_msg))
 }
 
 /// tries to register an ICA on the remote chain
@@ -118,6 +497,7 @@ fn try_register_ica(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Neutr
 
     // we are saving empty data here because we handle response of registering ICA in sudo_open_ack method
     INTERCHAIN_ACCOUNTS.save(deps.storage, key, &None)?;
+    ICA_REGISTRATION_SENT_AT.save(deps.storage, &env.block.time)?;
 
     Ok(Response::new()
         .add_attribute("method", "try_register_ica")
@@ -127,15 +507,13 @@ fn try_register_ica(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<Neutr
 fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<NeutronMsg>> {
     // first we verify whether the next contract is ready for receiving the funds
     let next_contract = NEXT_CONTRACT.load(deps.storage)?;
-    let deposit_address_query: Option<String> = deps
-        .querier
-        .query_wasm_smart(next_contract, &QueryMsg::DepositAddress {})?;
 
-    // if query returns None, then we error and wait
-    let Some(deposit_address) = deposit_address_query else {
-        return Err(NeutronError::Std(
-            StdError::not_found("Next contract is not ready for receiving the funds yet")
-        ))
+    // if the next contract is not ready, then we error and wait
+    let deposit_address = match DepositReady::check(&deps.querier, next_contract)? {
+        DepositReady::Ready { address } => address,
+        DepositReady::NotReady => {
+            return Err(ContractError::DepositAddressNotAvailable {}.into());
+        }
     };
 
     let port_id = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
@@ -145,34 +523,51 @@ fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<
         Some((address, controller_conn_id)) => {
             let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
 
-            let transfer_msg = MsgTransfer {
-                source_port: "transfer".to_string(),
-                source_channel: remote_chain_info.channel_id,
-                token: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
-                    denom: remote_chain_info.denom,
-                    amount: "10".to_string(), // TODO: remove hardcoded amount
-                }),
-                sender: address,
-                receiver: deposit_address,
-                timeout_height: None,
-                timeout_timestamp: env
-                    .block
-                    .time
-                    .plus_seconds(remote_chain_info.ica_timeout.u64())
-                    .plus_seconds(remote_chain_info.ibc_transfer_timeout.u64())
-                    .nanos(),
+            // we need to know the ICA balance before forwarding anything.
+            // the balance query gets registered once the ICA is opened. if
+            // that registration failed, we retry it here and forward on one
+            // of the following ticks once its results arrive.
+            let Some(query_id) = BALANCE_QUERY_ID.may_load(deps.storage)? else {
+                return try_register_balance_query(remote_chain_info, address);
             };
 
-            let protobuf_msg = to_proto_msg_transfer(transfer_msg)?;
+            let forward_coins =
+                get_forwardable_coins(deps.branch(), env.clone(), query_id, &remote_chain_info)?;
 
-            // tx to our ICA that wraps the transfer message defined above
+            // every denom gets its own transfer, all batched in a single ICA tx
+            let mut transfer_msgs: Vec<ProtobufAny> = vec![];
+            for coin in forward_coins.iter() {
+                let transfer_msg = MsgTransfer {
+                    source_port: "transfer".to_string(),
+                    source_channel: remote_chain_info.channel_id.to_string(),
+                    token: Some(cosmos_sdk_proto::cosmos::base::v1beta1::Coin {
+                        denom: coin.denom.to_string(),
+                        amount: coin.amount.to_string(),
+                    }),
+                    sender: address.to_string(),
+                    receiver: deposit_address.to_string(),
+                    timeout_height: None,
+                    timeout_timestamp: env
+                        .block
+                        .time
+                        .plus_seconds(remote_chain_info.ica_timeout.u64())
+                        .plus_seconds(remote_chain_info.ibc_transfer_timeout.u64())
+                        .nanos(),
+                };
+
+                transfer_msgs.push(to_proto_msg_transfer(transfer_msg)?);
+            }
+
+            assert_fee_balance(deps.as_ref(), &env, &remote_chain_info.ibc_fee)?;
+
+            // tx to our ICA that wraps the transfer messages defined above
             let submit_msg = NeutronMsg::submit_tx(
                 controller_conn_id,
                 INTERCHAIN_ACCOUNT_ID.to_string(),
-                vec![protobuf_msg],
+                transfer_msgs,
                 "".to_string(),
                 remote_chain_info.ica_timeout.u64(),
-                remote_chain_info.ibc_fee,
+                remote_chain_info.ibc_fee.clone(),
             );
 
             // sudo callback msg
@@ -181,12 +576,19 @@ fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<
                 submit_msg,
                 SudoPayload {
                     port_id,
-                    message: "try_forward_funds".to_string(),
+                    message: FORWARD_FUNDS_PAYLOAD.to_string(),
+                    coins: forward_coins.to_vec(),
+                    ibc_fee: Some(remote_chain_info.ibc_fee),
                 },
             )?;
 
+            // no more transfers until this one is acknowledged or failed
+            CONTRACT_STATE.save(deps.storage, &ContractState::ForwardPending)?;
+
+            let forwarded: Vec<String> = forward_coins.iter().map(|c| c.to_string()).collect();
             Ok(Response::default()
                 .add_attribute("method", "try_forward_funds")
+                .add_attribute("coins", forwarded.join(","))
                 .add_submessage(submsg))
         }
         None => {
@@ -201,8 +603,103 @@ fn try_forward_funds(env: Env, mut deps: ExecuteDeps) -> NeutronResult<Response<
     }
 }
 
+/// registers an interchain query for the balances of our ICA on the remote chain
+fn try_register_balance_query(
+    remote_chain_info: RemoteChainInfo,
+    ica_address: String,
+) -> NeutronResult<Response<NeutronMsg>> {
+    Ok(Response::default()
+        .add_attribute("method", "try_register_balance_query")
+        .add_submessage(get_balance_query_msg(&remote_chain_info, &ica_address)?))
+}
+
+fn get_balance_query_msg(
+    remote_chain_info: &RemoteChainInfo,
+    ica_address: &str,
+) -> NeutronResult<SubMsg<NeutronMsg>> {
+    // a single query keeps track of all denoms, one KV key per denom
+    let ica_address_bytes = decode_and_convert(ica_address)?;
+    let mut kv_keys: Vec<KVKey> = vec![];
+    for denom in remote_chain_info.denoms.iter() {
+        kv_keys.push(KVKey {
+            path: BANK_STORE_KEY.to_string(),
+            key: Binary(create_account_denom_balance_key(&ica_address_bytes, denom)?),
+        });
+    }
+
+    let register_msg = NeutronMsg::register_interchain_query(
+        QueryPayload::KV(kv_keys),
+        remote_chain_info.connection_id.clone(),
+        remote_chain_info.icq_update_period.u64(),
+    )?;
+
+    // query id is only known after the registration, so we get it in the
+    // reply. a failed registration must not revert the ICA being opened,
+    // so we get the reply on errors as well.
+    Ok(SubMsg::reply_always(register_msg, BALANCE_QUERY_REPLY_ID))
+}
+
+/// reads the latest ICA balances from the interchain query result and returns
+/// the coins that can be forwarded (balances above their reserves).
+/// errors if there is nothing new to forward so that the tick can be retried.
+fn get_forwardable_coins(
+    deps: ExecuteDeps,
+    env: Env,
+    query_id: u64,
+    remote_chain_info: &RemoteChainInfo,
+) -> NeutronResult<Vec<Coin>> {
+    let balance_response = query_balance(deps.as_ref(), env, query_id)?;
+
+    // ICQ results are only updated every `icq_update_period` blocks. if we
+    // already forwarded the balances from this result, the ICA may no longer
+    // hold them and we wait for a fresh one.
+    if let Some(last_height) = LAST_FORWARDED_HEIGHT.may_load(deps.storage)? {
+        if balance_response.last_submitted_local_height <= last_height {
+            return Err(NeutronError::Std(StdError::generic_err(
+                "ICA balance has not been updated since the last forward",
+            )));
+        }
+    }
+
+    let mut forward_coins: Vec<Coin> = vec![];
+    for coin in balance_response.balances.coins {
+        if !remote_chain_info.denoms.contains(&coin.denom) {
+            continue;
+        }
+
+        let reserve = remote_chain_info
+            .reserves
+            .iter()
+            .find(|reserve| reserve.denom == coin.denom)
+            .map(|reserve| reserve.amount)
+            .unwrap_or_default();
+
+        let amount = coin.amount.saturating_sub(reserve);
+        if !amount.is_zero() {
+            forward_coins.push(Coin {
+                denom: coin.denom,
+                amount,
+            });
+        }
+    }
+
+    if forward_coins.is_empty() {
+        return Err(NeutronError::Std(StdError::generic_err(
+            "ICA balances do not exceed their reserves",
+        )));
+    }
+
+    LAST_FORWARDED_HEIGHT.save(deps.storage, &balance_response.last_submitted_local_height)?;
+
+    Ok(forward_coins)
+}
+
 pub fn to_proto_msg_transfer(msg: impl Message) -> NeutronResult<ProtobufAny> {
-    // Serialize the Transfer message
+    to_proto_any(msg, "/ibc.applications.transfer.v1.MsgTransfer")
+}
+
+pub fn to_proto_any(msg: impl Message, type_url: &str) -> NeutronResult<ProtobufAny> {
+    // Serialize the message
     let mut buf = Vec::new();
     buf.reserve(msg.encoded_len());
     if let Err(e) = msg.encode(&mut buf) {
@@ -210,7 +707,7 @@ pub fn to_proto_msg_transfer(msg: impl Message) -> NeutronResult<ProtobufAny> {
     }
 
     Ok(ProtobufAny {
-        type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+        type_url: type_url.to_string(),
         value: Binary::from(buf),
     })
 }
@@ -220,8 +717,63 @@ fn msg_with_sudo_callback<C: Into<CosmosMsg<T>>, T>(
     msg: C,
     payload: SudoPayload,
 ) -> StdResult<SubMsg<T>> {
-    save_reply_payload(deps.storage, payload)?;
-    Ok(SubMsg::reply_on_success(msg, SUDO_PAYLOAD_REPLY_ID))
+    let reply_id = save_reply_payload(deps.storage, payload)?;
+    Ok(SubMsg::reply_on_success(msg, reply_id))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: ExecuteDeps, _env: Env, msg: MigrateMsg) -> NeutronResult<Response> {
+    deps.api.debug("WASMDEBUG: migrate");
+
+    // we only migrate forwarders, and never to an older version
+    let (stored, stored_version) = assert_migration(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if stored_version < semver::Version::new(1, 1, 0) {
+        // v1.0.0 forwarded a single denom and did not query the ICA balance
+        let Some(icq_update_period) = msg.icq_update_period else {
+            return Err(NeutronError::Std(StdError::generic_err(
+                "icq_update_period is required to migrate from 1.0.0",
+            )));
+        };
+        let legacy_info = LEGACY_REMOTE_CHAIN_INFO.load(deps.storage)?;
+        REMOTE_CHAIN_INFO.save(
+            deps.storage,
+            &RemoteChainInfo {
+                connection_id: legacy_info.connection_id,
+                channel_id: legacy_info.channel_id,
+                denoms: vec![legacy_info.denom],
+                ibc_transfer_timeout: legacy_info.ibc_transfer_timeout,
+                ica_timeout: legacy_info.ica_timeout,
+                ibc_fee: legacy_info.ibc_fee,
+                icq_update_period,
+                reserves: vec![],
+            },
+        )?;
+        FORWARDING_MODE.save(deps.storage, &ForwardingMode::Recurring)?;
+        RETRY_POLICY.save(deps.storage, &RetryPolicy::default())?;
+
+        // payloads of v1.0.0 were never read nor removed
+        let legacy_payloads = LEGACY_SUDO_PAYLOAD
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for key in legacy_payloads {
+            LEGACY_SUDO_PAYLOAD.remove(deps.storage, key);
+        }
+        LEGACY_REPLY_ID_STORAGE.remove(deps.storage);
+    }
+
+    if let Some(owner) = msg.owner {
+        if OWNER.may_load(deps.storage)?.is_none() {
+            OWNER.save(deps.storage, &deps.api.addr_validate(&owner)?)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "ibc_forwarder_migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -250,7 +802,92 @@ pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> NeutronResult<Binary>
         QueryMsg::IcaAddress {} => Ok(to_binary(&get_ica(deps, &env, INTERCHAIN_ACCOUNT_ID)?.0)?),
         QueryMsg::RemoteChainInfo {} => Ok(to_binary(&REMOTE_CHAIN_INFO.may_load(deps.storage)?)?),
         QueryMsg::ContractState {} => Ok(to_binary(&CONTRACT_STATE.may_load(deps.storage)?)?),
+        QueryMsg::ForwardingMode {} => Ok(to_binary(&FORWARDING_MODE.may_load(deps.storage)?)?),
+        QueryMsg::ForwardedTotals {} => Ok(to_binary(&query_forwarded_totals(deps)?)?),
+        QueryMsg::FeeSpend {} => Ok(to_binary(&FeeSpendResponse {
+            paid: query_fee_totals(deps, FEES_PAID)?,
+            refunded: query_fee_totals(deps, FEES_REFUNDED)?,
+        })?),
+        QueryMsg::Ownership {} => Ok(to_binary(&query_ownership(deps)?)?),
+        QueryMsg::ChannelHealth {} => Ok(to_binary(
+            &CHANNEL_HEALTH.may_load(deps.storage)?.unwrap_or_default(),
+        )?),
+        QueryMsg::FailedRequests { start_after, limit } => Ok(to_binary(&query_failed_requests(
+            deps,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::RetryStatus {} => Ok(to_binary(&RetryStatus {
+            retry_policy: RETRY_POLICY.may_load(deps.storage)?.unwrap_or_default(),
+            consecutive_failures: CONSECUTIVE_FAILURES
+                .may_load(deps.storage)?
+                .unwrap_or_default(),
+            next_attempt: NEXT_ATTEMPT.may_load(deps.storage)?,
+        })?),
+        QueryMsg::Paused {} => Ok(to_binary(&is_paused(deps.storage)?)?),
+        QueryMsg::Guardian {} => Ok(to_binary(&GUARDIAN.may_load(deps.storage)?)?),
+        QueryMsg::KeeperTip {} => Ok(to_binary(&KeeperTipResponse {
+            keeper_tip: KEEPER_TIP.may_load(deps.storage)?,
+            last_paid_tick: LAST_PAID_TICK.may_load(deps.storage)?,
+        })?),
+        QueryMsg::CronSchedule {} => Ok(to_binary(&CRON_SCHEDULE.may_load(deps.storage)?)?),
+        QueryMsg::RemoteBalance {} => Ok(to_binary(&REMOTE_BALANCE.may_load(deps.storage)?)?),
+        QueryMsg::InFlightRequests { start_after, limit } => Ok(to_binary(
+            &query_in_flight_requests(deps, start_after, limit)?,
+        )?),
+    }
+}
+
+fn query_forwarded_totals(deps: QueryDeps) -> StdResult<Vec<Coin>> {
+    let mut totals: Vec<Coin> = vec![];
+
+    for entry in FORWARDED_TOTALS.range(deps.storage, None, None, Order::Ascending) {
+        let (denom, amount) = entry?;
+        totals.push(Coin { denom, amount });
     }
+
+    Ok(totals)
+}
+
+fn query_failed_requests(
+    deps: QueryDeps,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FailedRequest>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    FAILED_REQUESTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| entry.map(|(_, request)| request))
+        .collect()
+}
+
+fn query_in_flight_requests(
+    deps: QueryDeps,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PendingRequest>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    PENDING_REQUESTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| entry.map(|(_, request)| request))
+        .collect()
+}
+
+fn query_fee_totals(deps: QueryDeps, totals: Map<String, Uint128>) -> StdResult<Vec<Coin>> {
+    let mut coins: Vec<Coin> = vec![];
+
+    for entry in totals.range(deps.storage, None, None, Order::Ascending) {
+        let (denom, amount) = entry?;
+        coins.push(Coin { denom, amount });
+    }
+
+    Ok(coins)
 }
 
 fn get_ica(
@@ -266,16 +903,37 @@ fn get_ica(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response> {
+pub fn sudo(
+    deps: ExecuteDeps,
+    env: Env,
+    msg: ContractSudoMsg,
+) -> NeutronResult<Response<NeutronMsg>> {
     deps.api
         .debug(format!("WASMDEBUG: sudo: received sudo msg: {msg:?}").as_str());
 
+    match msg {
+        ContractSudoMsg::Cron(CronSudoMsg::CronTick {}) => sudo_cron_tick(deps, env),
+        ContractSudoMsg::Neutron(msg) => Ok(sudo_neutron(deps, env, msg)?),
+    }
+}
+
+/// advances the state machine whenever the cron schedule of the
+/// forwarder fires. the tick has no sender, so nobody is tipped.
+fn sudo_cron_tick(deps: ExecuteDeps, env: Env) -> NeutronResult<Response<NeutronMsg>> {
+    let Some(schedule) = CRON_SCHEDULE.may_load(deps.storage)? else {
+        return Err(ContractError::CronNotConfigured {}.into());
+    };
+
+    Ok(try_tick(deps, env, None)?.add_attribute("cron_schedule", schedule))
+}
+
+fn sudo_neutron(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
     match msg {
         // For handling successful (non-error) acknowledgements.
         SudoMsg::Response { request, data } => sudo_response(deps, request, data),
 
         // For handling error acknowledgements.
-        SudoMsg::Error { request, details } => sudo_error(deps, request, details),
+        SudoMsg::Error { request, details } => sudo_error(deps, env, request, details),
 
         // For handling error timeouts.
         SudoMsg::Timeout { request } => sudo_timeout(deps, env, request),
@@ -294,6 +952,9 @@ pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response> {
             counterparty_channel_id,
             counterparty_version,
         ),
+
+        // For handling updated results of the balance query
+        SudoMsg::KVQueryResult { query_id } => sudo_kv_query_result(deps, env, query_id),
         _ => Ok(Response::default()),
     }
 }
@@ -301,12 +962,12 @@ pub fn sudo(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response> {
 // handler
 fn sudo_open_ack(
     deps: ExecuteDeps,
-    _env: Env,
+    env: Env,
     port_id: String,
-    _channel_id: String,
+    channel_id: String,
     _counterparty_channel_id: String,
     counterparty_version: String,
-) -> StdResult<Response> {
+) -> StdResult<Response<NeutronMsg>> {
     // The version variable contains a JSON value with multiple fields,
     // including the generated account address.
     let parsed_version: Result<OpenAckVersion, _> =
@@ -326,39 +987,185 @@ fn sudo_open_ack(
             parsed_version.controller_connection_id,
         )),
     )?;
+    ICA_REGISTRATION_SENT_AT.remove(deps.storage);
+
+    // the ICA balance is tracked from the moment the ICA exists. a reopened
+    // channel keeps the ICA address, so its query is already registered.
+    let mut response = Response::default().add_attribute("method", "sudo_open_ack");
+    if BALANCE_QUERY_ID.may_load(deps.storage)?.is_none() {
+        let remote_chain_info = REMOTE_CHAIN_INFO.load(deps.storage)?;
+        let register_msg = get_balance_query_msg(&remote_chain_info, &parsed_version.address)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+        response = response.add_submessage(register_msg);
+    }
+
+    let mut health = CHANNEL_HEALTH.may_load(deps.storage)?.unwrap_or_default();
+    if health.recovering {
+        health.recovering = false;
+        health.recoveries += 1;
+        health.last_recovery = Some(env.block.time);
+    }
+    health.channel_id = Some(channel_id);
+    CHANNEL_HEALTH.save(deps.storage, &health)?;
+
     CONTRACT_STATE.save(deps.storage, &ContractState::IcaCreated)?;
 
-    Ok(Response::default().add_attribute("method", "sudo_open_ack"))
+    Ok(response)
+}
+
+fn sudo_kv_query_result(
+    deps: ExecuteDeps,
+    env: Env,
+    query_id: u64,
+) -> StdResult<Response<NeutronMsg>> {
+    deps.api
+        .debug(format!("WASMDEBUG: sudo_kv_query_result: query_id: {query_id}").as_str());
+
+    if BALANCE_QUERY_ID.may_load(deps.storage)? != Some(query_id) {
+        return Ok(Response::default());
+    }
+
+    let balance_response = query_balance(deps.as_ref(), env, query_id)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    REMOTE_BALANCE.save(
+        deps.storage,
+        &RemoteBalanceResponse {
+            balances: balance_response.balances.coins,
+            last_updated_height: balance_response.last_submitted_local_height,
+        },
+    )?;
+
+    Ok(Response::default()
+        .add_attribute("method", "sudo_kv_query_result")
+        .add_attribute(
+            "last_updated_height",
+            balance_response.last_submitted_local_height.to_string(),
+        ))
 }
 
-fn sudo_response(deps: ExecuteDeps, request: RequestPacket, data: Binary) -> StdResult<Response> {
+fn sudo_response(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+    data: Binary,
+) -> StdResult<Response<NeutronMsg>> {
     deps.api
         .debug(format!("WASMDEBUG: sudo_response: sudo received: {request:?} {data:?}").as_str());
 
     // either of these errors will close the channel
-    request
+    let seq_id = request
         .sequence
         .ok_or_else(|| StdError::generic_err("sequence not found"))?;
 
-    request
+    let channel_id = request
         .source_channel
         .ok_or_else(|| StdError::generic_err("channel_id not found"))?;
 
+    // a successfully acknowledged transfer either completes the forwarder
+    // or gets it ready for the next transfer
+    let payload = take_sudo_payload(deps.storage, channel_id, seq_id)?;
+    if let Some(ibc_fee) = payload.as_ref().and_then(|p| p.ibc_fee.as_ref()) {
+        record_ibc_fees(deps.storage, &ibc_fee.ack_fee, &ibc_fee.timeout_fee)?;
+    }
+    if let Some(payload) = payload.filter(|p| p.message == FORWARD_FUNDS_PAYLOAD) {
+        // funds of earlier failed transfers were still on the ICA and
+        // went out with this one, so nothing is outstanding anymore
+        let failed = FAILED_REQUESTS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for key in failed {
+            FAILED_REQUESTS.remove(deps.storage, key);
+        }
+        CONSECUTIVE_FAILURES.remove(deps.storage);
+        NEXT_ATTEMPT.remove(deps.storage);
+
+        for coin in payload.coins {
+            FORWARDED_TOTALS.update(deps.storage, coin.denom, |total| -> StdResult<_> {
+                Ok(total.unwrap_or_default().checked_add(coin.amount)?)
+            })?;
+        }
+
+        let next_state = match FORWARDING_MODE.may_load(deps.storage)? {
+            Some(ForwardingMode::OneShot) => ContractState::Complete,
+            _ => ContractState::IcaCreated,
+        };
+        CONTRACT_STATE.save(deps.storage, &next_state)?;
+    }
+
     Ok(Response::default().add_attribute("method", "sudo_response"))
 }
 
-fn sudo_timeout(deps: ExecuteDeps, _env: Env, request: RequestPacket) -> StdResult<Response> {
+/// acknowledged packets pay the ack fee to the relayer and refund the
+/// timeout fee, timed out packets do the opposite
+fn record_ibc_fees(storage: &mut dyn Storage, paid: &[Coin], refunded: &[Coin]) -> StdResult<()> {
+    for coin in paid {
+        FEES_PAID.update(storage, coin.denom.clone(), |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(coin.amount)?)
+        })?;
+    }
+    for coin in refunded {
+        FEES_REFUNDED.update(storage, coin.denom.clone(), |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(coin.amount)?)
+        })?;
+    }
+    Ok(())
+}
+
+fn sudo_timeout(
+    deps: ExecuteDeps,
+    env: Env,
+    request: RequestPacket,
+) -> StdResult<Response<NeutronMsg>> {
     deps.api
         .debug(format!("WASMDEBUG: sudo timeout request: {request:?}").as_str());
 
-    // revert the state to Instantiated to force re-creation of ICA
-    CONTRACT_STATE.save(deps.storage, &ContractState::Instantiated)?;
+    // on timeout the relayer gets the timeout fee and the ack fee is refunded.
+    // a timed out forward left the funds on the ICA just like a failed one,
+    // so it backs off the same way before they are forwarded again.
+    if let (Some(seq_id), Some(channel_id)) = (request.sequence, request.source_channel) {
+        let payload = take_sudo_payload(deps.storage, channel_id.clone(), seq_id)?;
+        if let Some(ibc_fee) = payload.as_ref().and_then(|p| p.ibc_fee.as_ref()) {
+            record_ibc_fees(deps.storage, &ibc_fee.timeout_fee, &ibc_fee.ack_fee)?;
+        }
+        if let Some(payload) = payload.filter(|p| p.message == FORWARD_FUNDS_PAYLOAD) {
+            record_failed_forward(
+                deps.storage,
+                &env,
+                channel_id,
+                seq_id,
+                payload,
+                "timeout".to_string(),
+            )?;
+        }
+    }
+
+    let mut health = CHANNEL_HEALTH.may_load(deps.storage)?.unwrap_or_default();
+    health.timeouts += 1;
+    health.last_timeout = Some(env.block.time);
+
+    // the ICA channel is ordered, so a timeout closes it. if we know the
+    // ICA address we recover the channel and keep the address, otherwise
+    // we revert the state to Instantiated to force re-creation of ICA.
+    let port_id = get_port_id(env.contract.address.as_str(), INTERCHAIN_ACCOUNT_ID);
+    let next_state = match INTERCHAIN_ACCOUNTS.may_load(deps.storage, port_id)? {
+        Some(Some(_)) => {
+            health.recovering = true;
+            ContractState::IcaRecovering
+        }
+        _ => ContractState::Instantiated,
+    };
+    CHANNEL_HEALTH.save(deps.storage, &health)?;
+    CONTRACT_STATE.save(deps.storage, &next_state)?;
 
     // returning Ok as this is anticipated. channel is already closed.
     Ok(Response::default())
 }
 
-fn sudo_error(deps: ExecuteDeps, request: RequestPacket, details: String) -> StdResult<Response> {
+fn sudo_error(
+    deps: ExecuteDeps,
+    env: Env,
+    request: RequestPacket,
+    details: String,
+) -> StdResult<Response<NeutronMsg>> {
     deps.api
         .debug(format!("WASMDEBUG: sudo error: {details}").as_str());
 
@@ -366,19 +1173,70 @@ fn sudo_error(deps: ExecuteDeps, request: RequestPacket, details: String) -> Std
         .debug(format!("WASMDEBUG: request packet: {request:?}").as_str());
 
     // either of these errors will close the channel
-    request
+    let seq_id = request
         .sequence
         .ok_or_else(|| StdError::generic_err("sequence not found"))?;
 
-    request
+    let channel_id = request
         .source_channel
         .ok_or_else(|| StdError::generic_err("channel_id not found"))?;
 
+    // funds are still on the ICA, so we get ready to forward them again
+    // once the backoff of the retry policy has passed
+    let payload = take_sudo_payload(deps.storage, channel_id.clone(), seq_id)?;
+    if let Some(ibc_fee) = payload.as_ref().and_then(|p| p.ibc_fee.as_ref()) {
+        record_ibc_fees(deps.storage, &ibc_fee.ack_fee, &ibc_fee.timeout_fee)?;
+    }
+    if let Some(payload) = payload.filter(|p| p.message == FORWARD_FUNDS_PAYLOAD) {
+        record_failed_forward(deps.storage, &env, channel_id, seq_id, payload, details)?;
+        CONTRACT_STATE.save(deps.storage, &ContractState::IcaCreated)?;
+    }
+
     Ok(Response::default().add_attribute("method", "sudo_error"))
 }
 
-pub fn save_reply_payload(store: &mut dyn Storage, payload: SudoPayload) -> StdResult<()> {
-    REPLY_ID_STORAGE.save(store, &to_vec(&payload)?)
+/// keeps track of the failed forward and delays the next one by the
+/// backoff of the retry policy
+fn record_failed_forward(
+    storage: &mut dyn Storage,
+    env: &Env,
+    channel_id: String,
+    seq_id: u64,
+    payload: SudoPayload,
+    error: String,
+) -> StdResult<()> {
+    FAILED_REQUESTS.save(
+        storage,
+        (channel_id.clone(), seq_id),
+        &FailedRequest {
+            channel_id,
+            seq_id,
+            error,
+            payload,
+            failed_at: env.block.time,
+        },
+    )?;
+
+    let failures = CONSECUTIVE_FAILURES
+        .may_load(storage)?
+        .unwrap_or_default()
+        .saturating_add(1);
+    let delay = RETRY_POLICY
+        .may_load(storage)?
+        .unwrap_or_default()
+        .delay(failures);
+    CONSECUTIVE_FAILURES.save(storage, &failures)?;
+    NEXT_ATTEMPT.save(storage, &env.block.time.plus_seconds(delay))
+}
+
+/// stores the payload under a fresh reply id and returns the id
+pub fn save_reply_payload(store: &mut dyn Storage, payload: SudoPayload) -> StdResult<u64> {
+    let reply_id = NEXT_REPLY_ID
+        .may_load(store)?
+        .unwrap_or(SUDO_PAYLOAD_REPLY_ID_START);
+    NEXT_REPLY_ID.save(store, &(reply_id + 1))?;
+    REPLY_PAYLOADS.save(store, reply_id, &payload)?;
+    Ok(reply_id)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -386,7 +1244,8 @@ pub fn reply(deps: ExecuteDeps, env: Env, msg: Reply) -> StdResult<Response> {
     deps.api
         .debug(format!("WASMDEBUG: reply msg: {msg:?}").as_str());
     match msg.id {
-        SUDO_PAYLOAD_REPLY_ID => prepare_sudo_payload(deps, env, msg),
+        BALANCE_QUERY_REPLY_ID => handle_balance_query_registration(deps, msg),
+        id if id >= SUDO_PAYLOAD_REPLY_ID_START => prepare_sudo_payload(deps, env, msg),
         _ => Err(StdError::generic_err(format!(
             "unsupported reply message id {}",
             msg.id
@@ -394,8 +1253,8 @@ pub fn reply(deps: ExecuteDeps, env: Env, msg: Reply) -> StdResult<Response> {
     }
 }
 
-fn prepare_sudo_payload(mut deps: ExecuteDeps, _env: Env, msg: Reply) -> StdResult<Response> {
-    let payload = read_reply_payload(deps.storage)?;
+fn prepare_sudo_payload(deps: ExecuteDeps, env: Env, msg: Reply) -> StdResult<Response> {
+    let payload = read_reply_payload(deps.storage, msg.id)?;
     let resp: MsgSubmitTxResponse = serde_json_wasm::from_slice(
         msg.result
             .into_result()
@@ -409,13 +1268,58 @@ fn prepare_sudo_payload(mut deps: ExecuteDeps, _env: Env, msg: Reply) -> StdResu
         .debug(format!("WASMDEBUG: reply msg: {resp:?}").as_str());
     let seq_id = resp.sequence_id;
     let channel_id = resp.channel;
-    save_sudo_payload(deps.branch().storage, channel_id, seq_id, payload)?;
+    save_sudo_payload(deps.storage, channel_id, seq_id, payload, env.block.time)?;
     Ok(Response::new())
 }
 
-pub fn read_reply_payload(store: &mut dyn Storage) -> StdResult<SudoPayload> {
-    let data = REPLY_ID_STORAGE.load(store)?;
-    from_binary(&Binary(data))
+fn handle_balance_query_registration(deps: ExecuteDeps, msg: Reply) -> StdResult<Response> {
+    // the registration is retried on the next forward attempt
+    let result = match msg.result.into_result() {
+        Ok(result) => result,
+        Err(err) => {
+            deps.api
+                .debug(format!("WASMDEBUG: balance query registration failed: {err}").as_str());
+            return Ok(Response::new()
+                .add_attribute("method", "handle_balance_query_registration")
+                .add_attribute("error", err));
+        }
+    };
+
+    let resp: MsgRegisterInterchainQueryResponse = serde_json_wasm::from_slice(
+        result
+            .data
+            .ok_or_else(|| StdError::generic_err("no result"))?
+            .as_slice(),
+    )
+    .map_err(|e| StdError::generic_err(format!("failed to parse response: {e:?}")))?;
+
+    BALANCE_QUERY_ID.save(deps.storage, &resp.id)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "handle_balance_query_registration")
+        .add_attribute("query_id", resp.id.to_string()))
+}
+
+/// loads and removes the payload stored for the reply id
+pub fn read_reply_payload(store: &mut dyn Storage, reply_id: u64) -> StdResult<SudoPayload> {
+    let payload = REPLY_PAYLOADS.load(store, reply_id)?;
+    REPLY_PAYLOADS.remove(store, reply_id);
+    Ok(payload)
+}
+
+/// loads and removes the pending request, as every request gets
+/// exactly one sudo callback
+pub fn take_sudo_payload(
+    store: &mut dyn Storage,
+    channel_id: String,
+    seq_id: u64,
+) -> StdResult<Option<SudoPayload>> {
+    let key = (channel_id, seq_id);
+    let Some(request) = PENDING_REQUESTS.may_load(store, key.clone())? else {
+        return Ok(None);
+    };
+    PENDING_REQUESTS.remove(store, key);
+    Ok(Some(request.payload))
 }
 
 pub fn save_sudo_payload(
@@ -423,6 +1327,16 @@ pub fn save_sudo_payload(
     channel_id: String,
     seq_id: u64,
     payload: SudoPayload,
+    submitted_at: Timestamp,
 ) -> StdResult<()> {
-    SUDO_PAYLOAD.save(store, (channel_id, seq_id), &to_vec(&payload)?)
+    PENDING_REQUESTS.save(
+        store,
+        (channel_id.clone(), seq_id),
+        &PendingRequest {
+            channel_id,
+            seq_id,
+            payload,
+            submitted_at,
+        },
+    )
 }
@@ -1,4 +1,6 @@
 use cosmwasm_std::StdError;
+use guild_interfaces::{admin::AdminError, fee::IbcFeeError};
+use neutron_sdk::NeutronError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,9 +8,38 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
-    #[error("Unauthorized")]
-    Unauthorized {},
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    IbcFee(#[from] IbcFeeError),
 
     #[error("Next contract is not ready for receiving the funds yet")]
     DepositAddressNotAvailable {},
+
+    #[error("Insufficient balance to pay the IBC fees")]
+    InsufficientFeeBalance {},
+
+    #[error("No cron schedule configured")]
+    CronNotConfigured {},
+
+    #[error("Invalid keeper tip")]
+    InvalidKeeperTip {},
+
+    #[error("Recovery address must be a bech32 address on the remote chain")]
+    InvalidRecoveryAddress {},
+
+    #[error("Can not withdraw while a forward is pending")]
+    ForwardPending {},
+}
+
+impl From<ContractError> for NeutronError {
+    fn from(err: ContractError) -> Self {
+        match err {
+            ContractError::Std(e) => NeutronError::Std(e),
+            ContractError::Admin(e) => e.into(),
+            ContractError::IbcFee(e) => e.into(),
+            e => NeutronError::Std(StdError::generic_err(e.to_string())),
+        }
+    }
 }
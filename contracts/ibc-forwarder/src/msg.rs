@@ -1,14 +1,18 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint64;
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128, Uint64};
+use guild_interfaces::{admin::OwnershipResponse, deposit::DepositAddressResponse};
 use neutron_sdk::bindings::msg::IbcFee;
 
+use crate::error::ContractError;
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub next_contract: String,
 
     pub remote_chain_connection_id: String,
     pub remote_chain_channel_id: String,
-    pub denom: String,
+    /// denoms on the remote chain to be forwarded from the ICA
+    pub denoms: Vec<String>,
 
     /// timeout in seconds. this is used to craft a timeout timestamp
     /// that will be attached to the IBC transfer message from the ICA
@@ -22,11 +26,131 @@ pub struct InstantiateMsg {
     /// channel closed. We can reopen the channel by reregistering
     /// the ICA with the same port id and connection id
     pub ica_timeout: Uint64,
+    /// update period (in blocks) of the interchain query that keeps
+    /// track of the ICA balances of `denoms` on the remote chain
+    pub icq_update_period: Uint64,
+    /// amounts to be left on the ICA per denom. only the balance above
+    /// the reserve gets forwarded. denoms without a reserve are
+    /// forwarded in full.
+    pub reserves: Option<Vec<Coin>>,
+    /// whether the forwarder completes after its first successful
+    /// transfer or keeps forwarding. defaults to `Recurring`
+    pub forwarding_mode: Option<ForwardingMode>,
+    /// fee attached to every ICA tx. must cover the feerefunder minimum
+    /// fees, which are used if no fee is provided
+    pub ibc_fee: Option<IbcFee>,
+    /// address allowed to update the IBC fee and the retry policy
+    pub owner: Option<String>,
+    /// backoff applied to forwarding after failed transfers.
+    /// defaults to 60 seconds doubling up to an hour.
+    pub retry_policy: Option<RetryPolicy>,
+    /// address allowed to pause and unpause the forwarder besides the owner
+    pub guardian: Option<String>,
+    /// tip paid to the sender of a tick. no tips are paid if not provided
+    pub keeper_tip: Option<KeeperTip>,
+    /// name of the neutron cron schedule ticking the forwarder.
+    /// cron ticks are rejected if not provided
+    pub cron_schedule: Option<String>,
+}
+
+/// flat untrn tip paid out of the forwarder balance to whoever ticks
+/// the forwarder. only ticks that advance the forwarder are paid, at
+/// most one per `min_interval` seconds.
+#[cw_serde]
+pub struct KeeperTip {
+    pub amount: Uint128,
+    pub min_interval: Uint64,
+}
+
+impl KeeperTip {
+    /// a tip paid on every tick could be farmed by ticking
+    /// repeatedly within the same block
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.amount.is_zero() || self.min_interval.is_zero() {
+            return Err(ContractError::InvalidKeeperTip {});
+        }
+        Ok(())
+    }
+}
+
+/// exponential backoff for forwarding after failed transfers. the n-th
+/// consecutive failure delays the next forward by `base_delay * 2^(n-1)`
+/// seconds, capped at `max_delay`.
+#[cw_serde]
+pub struct RetryPolicy {
+    pub base_delay: Uint64,
+    pub max_delay: Uint64,
+}
+
+impl RetryPolicy {
+    /// delay in seconds after the given number of consecutive failures
+    pub fn delay(&self, failures: u32) -> u64 {
+        let factor = 1u64
+            .checked_shl(failures.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        self.base_delay
+            .u64()
+            .saturating_mul(factor)
+            .min(self.max_delay.u64())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            base_delay: Uint64::new(60),
+            max_delay: Uint64::new(3600),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum ForwardingMode {
+    /// forwarder moves to `Complete` after the first acknowledged transfer
+    OneShot,
+    /// forwarder returns to `IcaCreated` after every acknowledged transfer
+    Recurring,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
     Tick {},
+    /// updates the fee attached to ICA txs. owner only.
+    UpdateIbcFee {
+        ibc_fee: IbcFee,
+    },
+    /// proposes a new owner that has to accept the ownership. owner only.
+    TransferOwnership {
+        new_owner: String,
+    },
+    /// accepts the pending ownership transfer. pending owner only.
+    AcceptOwnership {},
+    /// updates the backoff applied after failed transfers. owner only.
+    UpdateRetryPolicy {
+        retry_policy: RetryPolicy,
+    },
+    /// stops ticks from advancing the forwarder. owner or guardian only.
+    Pause {},
+    /// lets ticks advance the forwarder again. owner or guardian only.
+    Unpause {},
+    /// sets or removes the guardian. owner only.
+    UpdateGuardian {
+        guardian: Option<String>,
+    },
+    /// sends the ICA balance of the forwarded denoms to an address on
+    /// the remote chain. rejected while a forward is pending. owner only.
+    EmergencyWithdraw {
+        recovery_address: String,
+    },
+    /// sets or removes the tip paid to the sender of a tick. owner only.
+    UpdateKeeperTip {
+        keeper_tip: Option<KeeperTip>,
+    },
+    /// sets or removes the name of the cron schedule ticking the
+    /// forwarder. owner only.
+    UpdateCronSchedule {
+        schedule: Option<String>,
+    },
 }
 
 #[cw_serde]
@@ -35,10 +159,22 @@ pub struct RemoteChainInfo {
     /// we wish to open an ICA
     pub connection_id: String,
     pub channel_id: String,
-    pub denom: String,
+    pub denoms: Vec<String>,
     pub ibc_transfer_timeout: Uint64,
     pub ica_timeout: Uint64,
     pub ibc_fee: IbcFee,
+    pub icq_update_period: Uint64,
+    pub reserves: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// update period of the balance query. required when migrating
+    /// from 1.0.0, which did not query the ICA balance.
+    pub icq_update_period: Option<Uint64>,
+    /// owner to set if the contract has none, e.g. when migrating
+    /// from 1.0.0 which had no owner
+    pub owner: Option<String>,
 }
 
 #[derive(QueryResponses)]
@@ -46,12 +182,112 @@ pub struct RemoteChainInfo {
 pub enum QueryMsg {
     #[returns(ContractState)]
     ContractState {},
-    #[returns(String)]
+    /// ICA address on the remote chain, see `guild_interfaces::deposit`
+    #[returns(DepositAddressResponse)]
     DepositAddress {},
     #[returns(RemoteChainInfo)]
     RemoteChainInfo {},
     #[returns(String)]
     IcaAddress {},
+    #[returns(ForwardingMode)]
+    ForwardingMode {},
+    /// amounts per denom that were successfully forwarded so far
+    #[returns(Vec<Coin>)]
+    ForwardedTotals {},
+    #[returns(FeeSpendResponse)]
+    FeeSpend {},
+    #[returns(OwnershipResponse)]
+    Ownership {},
+    #[returns(ChannelHealth)]
+    ChannelHealth {},
+    /// failed transfers that were not followed by a successful one yet
+    #[returns(Vec<FailedRequest>)]
+    FailedRequests {
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+    #[returns(RetryStatus)]
+    RetryStatus {},
+    #[returns(bool)]
+    Paused {},
+    #[returns(Option<Addr>)]
+    Guardian {},
+    /// latest ICA balances reported by the balance query
+    #[returns(Option<RemoteBalanceResponse>)]
+    RemoteBalance {},
+    /// submitted ICA txs that are waiting for their acknowledgement
+    #[returns(Vec<PendingRequest>)]
+    InFlightRequests {
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+    #[returns(KeeperTipResponse)]
+    KeeperTip {},
+    /// name of the cron schedule ticking the forwarder
+    #[returns(Option<String>)]
+    CronSchedule {},
+}
+
+pub type KeeperTipResponse = guild_interfaces::keeper::KeeperTipResponse<KeeperTip>;
+
+#[cw_serde]
+pub struct RemoteBalanceResponse {
+    /// balances of the forwarded denoms held by the ICA
+    pub balances: Vec<Coin>,
+    /// local height at which the balances were submitted by the relayer
+    pub last_updated_height: u64,
+}
+
+/// ICA tx that was submitted and waits for its sudo callback
+#[cw_serde]
+pub struct PendingRequest {
+    pub channel_id: String,
+    pub seq_id: u64,
+    pub payload: SudoPayload,
+    pub submitted_at: Timestamp,
+}
+
+/// ICA tx that got acknowledged with an error or timed out
+#[cw_serde]
+pub struct FailedRequest {
+    pub channel_id: String,
+    pub seq_id: u64,
+    pub error: String,
+    pub payload: SudoPayload,
+    pub failed_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct RetryStatus {
+    pub retry_policy: RetryPolicy,
+    /// number of failed transfers since the last successful one
+    pub consecutive_failures: u32,
+    /// forwarding is on hold until this time
+    pub next_attempt: Option<Timestamp>,
+}
+
+/// timeout and recovery history of the ICA channel
+#[cw_serde]
+#[derive(Default)]
+pub struct ChannelHealth {
+    /// channel of the ICA, set once the channel is opened
+    pub channel_id: Option<String>,
+    /// whether the ICA channel is being recovered
+    pub recovering: bool,
+    /// number of ICA txs that timed out and closed the channel
+    pub timeouts: u64,
+    pub last_timeout: Option<Timestamp>,
+    /// number of times the channel was reopened after a timeout
+    pub recoveries: u64,
+    pub last_recovery: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct FeeSpendResponse {
+    /// fees paid to relayers, i.e. the net fee spend
+    pub paid: Vec<Coin>,
+    /// fees refunded by the feerefunder module
+    pub refunded: Vec<Coin>,
 }
 
 #[cw_serde]
@@ -70,6 +306,11 @@ pub enum ContractState {
     Instantiated,
     /// ICA was created, funds are ready to be forwarded
     IcaCreated,
+    /// transfer was submitted, waiting for its acknowledgement
+    ForwardPending,
+    /// ICA channel was closed by a timeout, ICA is being re-registered
+    /// on the same port and connection. the known ICA address is kept.
+    IcaRecovering,
     /// forwarder is complete
     Complete,
 }
@@ -80,4 +321,10 @@ pub enum ContractState {
 pub struct SudoPayload {
     pub message: String,
     pub port_id: String,
+    /// coins being forwarded by the transaction, if any
+    #[serde(default)]
+    pub coins: Vec<Coin>,
+    /// fee attached to the transaction, if any
+    #[serde(default)]
+    pub ibc_fee: Option<IbcFee>,
 }
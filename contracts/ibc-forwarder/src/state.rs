@@ -1,13 +1,36 @@
-use cosmwasm_std::Addr;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128, Uint64};
 use cw_storage_plus::{Item, Map};
+use neutron_sdk::bindings::msg::IbcFee;
 
-use crate::msg::{ContractState, RemoteChainInfo};
+use crate::msg::{
+    ChannelHealth, ContractState, FailedRequest, ForwardingMode, KeeperTip, PendingRequest,
+    RemoteBalanceResponse, RemoteChainInfo, RetryPolicy, SudoPayload,
+};
 
 /// tracks the current state of state machine
 pub const CONTRACT_STATE: Item<ContractState> = Item::new("contract_state");
 
 pub const NEXT_CONTRACT: Item<Addr> = Item::new("next_contract");
 
+/// tip paid to the sender of a tick
+pub const KEEPER_TIP: Item<KeeperTip> = Item::new("keeper_tip");
+
+/// time the pending ICA registration was sent, removed on its open ack
+pub const ICA_REGISTRATION_SENT_AT: Item<Timestamp> = Item::new("ica_registration_sent_at");
+
+/// name of the neutron cron schedule ticking the forwarder
+pub const CRON_SCHEDULE: Item<String> = Item::new("cron_schedule");
+
+/// denom -> IBC fees paid to relayers
+pub const FEES_PAID: Map<String, Uint128> = Map::new("fees_paid");
+
+/// denom -> IBC fees refunded by the feerefunder module
+pub const FEES_REFUNDED: Map<String, Uint128> = Map::new("fees_refunded");
+
+/// whether the forwarder completes after the first acknowledged transfer
+pub const FORWARDING_MODE: Item<ForwardingMode> = Item::new("forwarding_mode");
+
 /// information needed for an ibc transfer to the remote chain
 pub const REMOTE_CHAIN_INFO: Item<RemoteChainInfo> = Item::new("r_c_info");
 
@@ -15,5 +38,62 @@ pub const REMOTE_CHAIN_INFO: Item<RemoteChainInfo> = Item::new("r_c_info");
 pub const INTERCHAIN_ACCOUNTS: Map<String, Option<(String, String)>> =
     Map::new("interchain_accounts");
 
-pub const REPLY_ID_STORAGE: Item<Vec<u8>> = Item::new("reply_queue_id");
-pub const SUDO_PAYLOAD: Map<(String, u64), Vec<u8>> = Map::new("sudo_payload");
+/// timeout and recovery history of the ICA channel
+pub const CHANNEL_HEALTH: Item<ChannelHealth> = Item::new("channel_health");
+
+/// id of the interchain query that tracks the ICA balance on the remote chain
+pub const BALANCE_QUERY_ID: Item<u64> = Item::new("balance_query_id");
+
+/// latest result of the balance query, updated on every query result
+pub const REMOTE_BALANCE: Item<RemoteBalanceResponse> = Item::new("remote_balance");
+
+/// local height of the last balance query result that was forwarded.
+/// used to avoid forwarding the same (stale) balance twice.
+pub const LAST_FORWARDED_HEIGHT: Item<u64> = Item::new("last_forwarded_height");
+
+/// denom -> amount that was successfully forwarded to the next contract
+pub const FORWARDED_TOTALS: Map<String, Uint128> = Map::new("forwarded_totals");
+
+/// (channel_id, seq_id) -> failed ICA tx, kept until a transfer succeeds
+pub const FAILED_REQUESTS: Map<(String, u64), FailedRequest> = Map::new("failed_requests");
+
+/// backoff applied to forwarding after failed transfers
+pub const RETRY_POLICY: Item<RetryPolicy> = Item::new("retry_policy");
+
+/// number of failed transfers since the last successful one
+pub const CONSECUTIVE_FAILURES: Item<u32> = Item::new("consecutive_failures");
+
+/// forwarding is on hold until this time after a failed transfer
+pub const NEXT_ATTEMPT: Item<Timestamp> = Item::new("next_attempt");
+
+/// next reply id for ICA tx submissions. each submission gets its own
+/// id so that payloads of submissions in the same tx don't collide.
+pub const NEXT_REPLY_ID: Item<u64> = Item::new("next_reply_id");
+
+/// reply id -> payload of an ICA tx waiting for its submission reply
+pub const REPLY_PAYLOADS: Map<u64, SudoPayload> = Map::new("reply_payloads");
+
+/// (channel_id, seq_id) -> submitted ICA tx waiting for its sudo callback
+pub const PENDING_REQUESTS: Map<(String, u64), PendingRequest> = Map::new("pending_requests");
+
+/// remote chain info layout of v1.0.0 that only forwarded a single denom.
+/// only used to migrate the stored info.
+#[cw_serde]
+pub struct LegacyRemoteChainInfo {
+    pub connection_id: String,
+    pub channel_id: String,
+    pub denom: String,
+    pub ibc_transfer_timeout: Uint64,
+    pub ica_timeout: Uint64,
+    pub ibc_fee: IbcFee,
+}
+
+/// v1.0.0 layout of REMOTE_CHAIN_INFO
+pub const LEGACY_REMOTE_CHAIN_INFO: Item<LegacyRemoteChainInfo> = Item::new("r_c_info");
+
+/// v1.0.0 reply payload storage, replaced by REPLY_PAYLOADS
+pub const LEGACY_REPLY_ID_STORAGE: Item<Vec<u8>> = Item::new("reply_queue_id");
+
+/// v1.0.0 sudo payload storage that was never cleaned up, replaced by
+/// PENDING_REQUESTS
+pub const LEGACY_SUDO_PAYLOAD: Map<(String, u64), Vec<u8>> = Map::new("sudo_payload");
@@ -0,0 +1,76 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::coins;
+
+use crate::msg::{ContractState, ExecuteMsg};
+
+use super::{Suite, REMOTE_DENOM};
+
+const RECOVERY_ADDRESS: &str = "cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnrk363e";
+
+fn emergency_withdraw(suite: &mut Suite, recovery_address: &str) -> AnyResult<()> {
+    suite.execute(ExecuteMsg::EmergencyWithdraw {
+        recovery_address: recovery_address.to_string(),
+    })?;
+    Ok(())
+}
+
+#[test]
+fn rejects_invalid_recovery_addresses() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+
+    for recovery_address in [
+        "",
+        "cosmos",
+        // bad checksum
+        "cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnrk363f",
+        // address on neutron instead of the remote chain
+        "neutron1qqqsyqcyq5rqwzqfpg9scrgwpugpzysn8fcct7",
+    ] {
+        let err = emergency_withdraw(&mut suite, recovery_address).unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("Recovery address must be a bech32 address on the remote chain"));
+    }
+
+    emergency_withdraw(&mut suite, RECOVERY_ADDRESS).unwrap();
+}
+
+#[test]
+fn withdrawn_balances_are_not_forwarded() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    emergency_withdraw(&mut suite, RECOVERY_ADDRESS).unwrap();
+    suite.ack_last_tx();
+    assert_eq!(suite.contract_state(), ContractState::IcaCreated);
+
+    let err = suite.tick().unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("ICA balance has not been updated since the last forward"));
+}
+
+#[test]
+fn rejects_withdraw_while_forward_pending() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    suite.tick().unwrap();
+    assert_eq!(suite.contract_state(), ContractState::ForwardPending);
+
+    let err = emergency_withdraw(&mut suite, RECOVERY_ADDRESS).unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("Can not withdraw while a forward is pending"));
+
+    suite.ack_last_tx();
+    suite.set_ica_balances(15, coins(200, REMOTE_DENOM));
+    emergency_withdraw(&mut suite, RECOVERY_ADDRESS).unwrap();
+}
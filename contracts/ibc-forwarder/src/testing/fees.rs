@@ -0,0 +1,129 @@
+use cosmwasm_std::{coin, coins, Addr, Uint128};
+use cw_multi_test::Executor;
+use neutron_sdk::bindings::msg::IbcFee;
+
+use crate::msg::{ContractState, FeeSpendResponse, QueryMsg};
+
+use super::{forwarded_coins, Suite, REMOTE_DENOM};
+
+/// ack and timeout fees that tell the paid and refunded totals apart
+fn uneven_ibc_fee() -> IbcFee {
+    IbcFee {
+        recv_fee: vec![],
+        ack_fee: coins(1000, "untrn"),
+        timeout_fee: coins(1500, "untrn"),
+    }
+}
+
+fn fee_spend(suite: &Suite) -> FeeSpendResponse {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.forwarder, &QueryMsg::FeeSpend {})
+        .unwrap()
+}
+
+/// leaves the forwarder with the given untrn balance
+fn set_fee_balance(suite: &mut Suite, amount: u128) {
+    let balance = suite
+        .app
+        .wrap()
+        .query_balance(&suite.forwarder, "untrn")
+        .unwrap()
+        .amount;
+    let sink = Addr::unchecked("sink");
+    let amount = Uint128::new(amount);
+    if balance > amount {
+        suite
+            .app
+            .send_tokens(
+                suite.forwarder.clone(),
+                sink,
+                &[coin((balance - amount).u128(), "untrn")],
+            )
+            .unwrap();
+    } else if balance < amount {
+        suite
+            .app
+            .send_tokens(
+                sink,
+                suite.forwarder.clone(),
+                &[coin((amount - balance).u128(), "untrn")],
+            )
+            .unwrap();
+    }
+}
+
+fn assert_fees_refused(suite: &mut Suite) {
+    let err = suite.tick().unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("Insufficient balance to pay the IBC fees"));
+}
+
+#[test]
+fn acknowledged_forward_pays_the_ack_fee() {
+    let mut suite = Suite::with_config(|msg| msg.ibc_fee = Some(uneven_ibc_fee()));
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    suite.tick().unwrap();
+    suite.ack_last_tx();
+    assert_eq!(
+        fee_spend(&suite),
+        FeeSpendResponse {
+            paid: coins(1000, "untrn"),
+            refunded: coins(1500, "untrn"),
+        }
+    );
+
+    // the next forward needs both fees escrowed again
+    set_fee_balance(&mut suite, 2499);
+    suite.set_ica_balances(15, coins(500, REMOTE_DENOM));
+    assert_fees_refused(&mut suite);
+    assert_eq!(suite.contract_state(), ContractState::IcaCreated);
+
+    set_fee_balance(&mut suite, 2500);
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("500uatom".to_string()));
+}
+
+#[test]
+fn timed_out_forward_pays_the_timeout_fee() {
+    let mut suite = Suite::with_config(|msg| msg.ibc_fee = Some(uneven_ibc_fee()));
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    suite.tick().unwrap();
+    suite.timeout_last_tx();
+    assert_eq!(
+        fee_spend(&suite),
+        FeeSpendResponse {
+            paid: coins(1500, "untrn"),
+            refunded: coins(1000, "untrn"),
+        }
+    );
+
+    // the recovered channel does not forward without the fees either
+    suite.open_ica();
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(60);
+    });
+    set_fee_balance(&mut suite, 2499);
+    suite.set_ica_balances(15, coins(1000, REMOTE_DENOM));
+    assert_fees_refused(&mut suite);
+    assert_eq!(suite.contract_state(), ContractState::IcaCreated);
+
+    set_fee_balance(&mut suite, 2500);
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("1000uatom".to_string()));
+    suite.ack_last_tx();
+    assert_eq!(
+        fee_spend(&suite),
+        FeeSpendResponse {
+            paid: coins(2500, "untrn"),
+            refunded: coins(2500, "untrn"),
+        }
+    );
+}
@@ -0,0 +1,127 @@
+use cosmwasm_std::{coin, coins, Coin};
+
+use crate::msg::{ContractState, ForwardingMode, QueryMsg};
+
+use super::{attribute, forwarded_coins, Suite, REMOTE_DENOM};
+
+#[test]
+fn forwards_full_balance() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("1000uatom".to_string()));
+    assert_eq!(suite.contract_state(), ContractState::ForwardPending);
+
+    suite.ack_last_tx();
+    assert_eq!(suite.contract_state(), ContractState::IcaCreated);
+
+    let totals: Vec<Coin> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.forwarder, &QueryMsg::ForwardedTotals {})
+        .unwrap();
+    assert_eq!(totals, coins(1000, REMOTE_DENOM));
+}
+
+#[test]
+fn forwards_balance_above_reserve() {
+    let mut suite = Suite::new(Some(coins(400, REMOTE_DENOM)));
+    suite.open_ica();
+
+    suite.set_ica_balances(10, vec![coin(1000, REMOTE_DENOM), coin(50, "uosmo")]);
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("600uatom".to_string()));
+
+    suite.ack_last_tx();
+
+    // nothing above the reserve is left to forward
+    suite.set_ica_balances(15, coins(400, REMOTE_DENOM));
+    let err = suite.tick().unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("ICA balances do not exceed their reserves"));
+}
+
+#[test]
+fn rejects_stale_balance() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    suite.tick().unwrap();
+    suite.ack_last_tx();
+
+    // the result the last forward was based on must not be forwarded again
+    let err = suite.tick().unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("ICA balance has not been updated since the last forward"));
+    assert_eq!(suite.contract_state(), ContractState::IcaCreated);
+
+    suite.set_ica_balances(15, coins(200, REMOTE_DENOM));
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("200uatom".to_string()));
+}
+
+#[test]
+fn one_shot_completes_after_first_forward() {
+    let mut suite = Suite::with_config(|msg| msg.forwarding_mode = Some(ForwardingMode::OneShot));
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("1000uatom".to_string()));
+
+    suite.ack_last_tx();
+    assert_eq!(suite.contract_state(), ContractState::Complete);
+    let forwarded_sequence = suite.last_tx_request().sequence;
+
+    // later ticks neither forward new balances nor submit any txs
+    suite.set_ica_balances(15, coins(500, REMOTE_DENOM));
+    for _ in 0..2 {
+        let response = suite.tick().unwrap();
+        assert_eq!(forwarded_coins(&response), None);
+        assert_eq!(
+            attribute(&response, "contract_state"),
+            Some("completed".to_string())
+        );
+    }
+    assert_eq!(suite.contract_state(), ContractState::Complete);
+    assert_eq!(suite.last_tx_request().sequence, forwarded_sequence);
+}
+
+#[test]
+fn batches_denoms_above_their_reserves() {
+    let mut suite = Suite::with_config(|msg| {
+        msg.denoms = vec![REMOTE_DENOM.to_string(), "uosmo".to_string()];
+        msg.reserves = Some(vec![coin(400, REMOTE_DENOM), coin(100, "uosmo")]);
+    });
+    suite.open_ica();
+
+    // uosmo stays below its reserve and is left on the ICA
+    suite.set_ica_balances(10, vec![coin(1000, REMOTE_DENOM), coin(50, "uosmo")]);
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("600uatom".to_string()));
+    suite.ack_last_tx();
+
+    // both denoms go out in the same tx
+    suite.set_ica_balances(15, vec![coin(500, REMOTE_DENOM), coin(350, "uosmo")]);
+    let response = suite.tick().unwrap();
+    assert_eq!(
+        forwarded_coins(&response),
+        Some("100uatom,250uosmo".to_string())
+    );
+    assert_eq!(suite.last_tx_request().sequence, Some(2));
+    suite.ack_last_tx();
+
+    let totals: Vec<Coin> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.forwarder, &QueryMsg::ForwardedTotals {})
+        .unwrap();
+    assert_eq!(totals, vec![coin(700, REMOTE_DENOM), coin(250, "uosmo")]);
+}
@@ -0,0 +1,79 @@
+use cosmwasm_std::coins;
+use neutron_sdk::sudo::msg::SudoMsg;
+
+use crate::msg::{QueryMsg, RemoteBalanceResponse};
+
+use super::{forwarded_coins, Suite, BALANCE_QUERY_ID, REMOTE_DENOM};
+
+fn remote_balance(suite: &Suite) -> Option<RemoteBalanceResponse> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.forwarder, &QueryMsg::RemoteBalance {})
+        .unwrap()
+}
+
+fn submit_query_result(suite: &mut Suite, query_id: u64) {
+    suite
+        .app
+        .wasm_sudo(
+            suite.forwarder.clone(),
+            &SudoMsg::KVQueryResult { query_id },
+        )
+        .unwrap();
+}
+
+#[test]
+fn query_results_update_the_remote_balance() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+    assert_eq!(remote_balance(&suite), None);
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    submit_query_result(&mut suite, BALANCE_QUERY_ID);
+    assert_eq!(
+        remote_balance(&suite),
+        Some(RemoteBalanceResponse {
+            balances: coins(1000, REMOTE_DENOM),
+            last_updated_height: 10,
+        })
+    );
+
+    // results of queries the forwarder did not register are ignored
+    suite.set_ica_balances(15, coins(10, REMOTE_DENOM));
+    submit_query_result(&mut suite, BALANCE_QUERY_ID + 1);
+    assert_eq!(remote_balance(&suite).unwrap().last_updated_height, 10);
+
+    submit_query_result(&mut suite, BALANCE_QUERY_ID);
+    assert_eq!(
+        remote_balance(&suite),
+        Some(RemoteBalanceResponse {
+            balances: coins(10, REMOTE_DENOM),
+            last_updated_height: 15,
+        })
+    );
+}
+
+#[test]
+fn rejects_results_not_newer_than_the_last_forward() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    suite.tick().unwrap();
+    suite.ack_last_tx();
+
+    // neither the same nor an older result is forwarded again
+    for height in [10, 9] {
+        suite.set_ica_balances(height, coins(1000, REMOTE_DENOM));
+        let err = suite.tick().unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("ICA balance has not been updated since the last forward"));
+    }
+
+    suite.set_ica_balances(11, coins(300, REMOTE_DENOM));
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("300uatom".to_string()));
+}
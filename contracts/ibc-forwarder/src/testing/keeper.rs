@@ -0,0 +1,79 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{coins, Addr, Uint128, Uint64};
+use cw_multi_test::{AppResponse, Executor};
+
+use crate::msg::{ExecuteMsg, KeeperTip};
+
+use super::{Suite, REMOTE_DENOM};
+
+const KEEPER: &str = "keeper";
+
+fn keeper_tick(suite: &mut Suite) -> AnyResult<AppResponse> {
+    suite.app.execute_contract(
+        Addr::unchecked(KEEPER),
+        suite.forwarder.clone(),
+        &ExecuteMsg::Tick {},
+        &[],
+    )
+}
+
+fn update_keeper_tip(suite: &mut Suite, amount: u128, min_interval: u64) -> AnyResult<AppResponse> {
+    suite.execute(ExecuteMsg::UpdateKeeperTip {
+        keeper_tip: Some(KeeperTip {
+            amount: Uint128::new(amount),
+            min_interval: Uint64::new(min_interval),
+        }),
+    })
+}
+
+fn keeper_balance(suite: &Suite) -> Uint128 {
+    suite
+        .app
+        .wrap()
+        .query_balance(KEEPER, "untrn")
+        .unwrap()
+        .amount
+}
+
+#[test]
+fn rejects_invalid_keeper_tips() {
+    let mut suite = Suite::new(None);
+
+    for (amount, min_interval) in [(0, 60), (500, 0)] {
+        let err = update_keeper_tip(&mut suite, amount, min_interval).unwrap_err();
+        assert!(err.root_cause().to_string().contains("Invalid keeper tip"));
+    }
+    update_keeper_tip(&mut suite, 500, 60).unwrap();
+}
+
+#[test]
+fn tips_at_most_once_per_interval() {
+    let mut suite = Suite::new(None);
+    update_keeper_tip(&mut suite, 500, 60).unwrap();
+
+    // registering the ICA advances the forwarder
+    keeper_tick(&mut suite).unwrap();
+    assert_eq!(keeper_balance(&suite), Uint128::new(500));
+
+    suite.open_ica();
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    keeper_tick(&mut suite).unwrap();
+    assert_eq!(keeper_balance(&suite), Uint128::new(500));
+    suite.ack_last_tx();
+
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(60);
+    });
+    suite.set_ica_balances(15, coins(1000, REMOTE_DENOM));
+    keeper_tick(&mut suite).unwrap();
+    assert_eq!(keeper_balance(&suite), Uint128::new(1000));
+}
+
+#[test]
+fn rejects_overflowing_keeper_tip() {
+    let mut suite = Suite::new(None);
+    update_keeper_tip(&mut suite, u128::MAX, 60).unwrap();
+
+    let err = keeper_tick(&mut suite).unwrap_err();
+    assert!(err.root_cause().to_string().contains("Invalid keeper tip"));
+}
@@ -0,0 +1,176 @@
+use std::marker::PhantomData;
+
+use cosmwasm_std::{
+    testing::{mock_env, MockApi, MockQuerier, MockStorage},
+    Order, OwnedDeps, Uint64,
+};
+use cw2::{get_contract_version, set_contract_version};
+use neutron_sdk::bindings::query::NeutronQuery;
+
+use crate::{
+    contract::migrate,
+    msg::{ForwardingMode, MigrateMsg, RemoteChainInfo},
+    state::{
+        LegacyRemoteChainInfo, FORWARDING_MODE, LEGACY_REMOTE_CHAIN_INFO, LEGACY_REPLY_ID_STORAGE,
+        LEGACY_SUDO_PAYLOAD, REMOTE_CHAIN_INFO, RETRY_POLICY,
+    },
+};
+use guild_interfaces::admin::OWNER;
+
+use super::{ibc_fee, CONNECTION_ID, REMOTE_DENOM, TRANSFER_CHANNEL_ID};
+
+const CONTRACT_NAME: &str = "crates.io:ibc-forwarder";
+
+type NeutronDeps = OwnedDeps<MockStorage, MockApi, MockQuerier<NeutronQuery>, NeutronQuery>;
+
+fn neutron_deps() -> NeutronDeps {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: MockQuerier::new(&[]),
+        custom_query_type: PhantomData,
+    }
+}
+
+/// writes the storage layout of a v1.0.0 forwarder
+fn legacy_deps() -> NeutronDeps {
+    let mut deps = neutron_deps();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "1.0.0").unwrap();
+    LEGACY_REMOTE_CHAIN_INFO
+        .save(
+            deps.as_mut().storage,
+            &LegacyRemoteChainInfo {
+                connection_id: CONNECTION_ID.to_string(),
+                channel_id: TRANSFER_CHANNEL_ID.to_string(),
+                denom: REMOTE_DENOM.to_string(),
+                ibc_transfer_timeout: Uint64::new(600),
+                ica_timeout: Uint64::new(300),
+                ibc_fee: ibc_fee(),
+            },
+        )
+        .unwrap();
+    LEGACY_REPLY_ID_STORAGE
+        .save(deps.as_mut().storage, &b"payload".to_vec())
+        .unwrap();
+    for seq in 1..=3 {
+        LEGACY_SUDO_PAYLOAD
+            .save(
+                deps.as_mut().storage,
+                ("channel-10".to_string(), seq),
+                &b"payload".to_vec(),
+            )
+            .unwrap();
+    }
+    deps
+}
+
+#[test]
+fn migrates_from_v1_0_0() {
+    let mut deps = legacy_deps();
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            icq_update_period: Some(Uint64::new(5)),
+            owner: Some("owner".to_string()),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        REMOTE_CHAIN_INFO.load(deps.as_ref().storage).unwrap(),
+        RemoteChainInfo {
+            connection_id: CONNECTION_ID.to_string(),
+            channel_id: TRANSFER_CHANNEL_ID.to_string(),
+            denoms: vec![REMOTE_DENOM.to_string()],
+            ibc_transfer_timeout: Uint64::new(600),
+            ica_timeout: Uint64::new(300),
+            ibc_fee: ibc_fee(),
+            icq_update_period: Uint64::new(5),
+            reserves: vec![],
+        }
+    );
+    assert_eq!(
+        FORWARDING_MODE.load(deps.as_ref().storage).unwrap(),
+        ForwardingMode::Recurring
+    );
+    assert!(RETRY_POLICY
+        .may_load(deps.as_ref().storage)
+        .unwrap()
+        .is_some());
+    assert_eq!(OWNER.load(deps.as_ref().storage).unwrap().as_str(), "owner");
+
+    // v1.0.0 payloads are gone
+    assert!(LEGACY_REPLY_ID_STORAGE
+        .may_load(deps.as_ref().storage)
+        .unwrap()
+        .is_none());
+    assert!(LEGACY_SUDO_PAYLOAD
+        .keys(deps.as_ref().storage, None, None, Order::Ascending)
+        .next()
+        .is_none());
+
+    let version = get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn requires_icq_update_period_from_v1_0_0() {
+    let mut deps = legacy_deps();
+
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            icq_update_period: None,
+            owner: None,
+        },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("icq_update_period is required"));
+}
+
+#[test]
+fn rejects_other_contracts() {
+    let mut deps = legacy_deps();
+    set_contract_version(
+        deps.as_mut().storage,
+        "crates.io:protocol-guild-splitter",
+        "1.0.0",
+    )
+    .unwrap();
+
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            icq_update_period: Some(Uint64::new(5)),
+            owner: None,
+        },
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("cannot migrate from crates.io:protocol-guild-splitter 1.0.0"));
+}
+
+#[test]
+fn rejects_newer_versions() {
+    let mut deps = legacy_deps();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            icq_update_period: Some(Uint64::new(5)),
+            owner: None,
+        },
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("cannot migrate from crates.io:ibc-forwarder 99.0.0"));
+}
@@ -0,0 +1,259 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{
+    coin, coins, to_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdResult,
+};
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
+use guild_interfaces::deposit::{DepositAddressResponse, DepositQueryMsg};
+use guild_testing::{last_ica_sequence, neutron_app, set_icq_balances, NeutronApp, NeutronMock};
+use neutron_sdk::{
+    bindings::{
+        msg::{IbcFee, NeutronMsg},
+        query::NeutronQuery,
+    },
+    interchain_txs::helpers::get_port_id,
+    sudo::msg::{RequestPacket, SudoMsg},
+};
+
+use crate::{
+    contract::{execute, instantiate, migrate, query, reply, sudo},
+    msg::{ContractState, ExecuteMsg, InstantiateMsg, OpenAckVersion, QueryMsg},
+};
+
+mod emergency;
+mod fees;
+mod forward;
+mod icq;
+mod keeper;
+mod migrate;
+mod recovery;
+mod requests;
+mod retry;
+
+const CONNECTION_ID: &str = "connection-0";
+const TRANSFER_CHANNEL_ID: &str = "channel-1";
+const ICA_CHANNEL_ID: &str = "channel-10";
+const ICA_ADDRESS: &str = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusqqlvp8l";
+const REMOTE_DENOM: &str = "uatom";
+const BALANCE_QUERY_ID: u64 = 1;
+
+fn ibc_fee() -> IbcFee {
+    IbcFee {
+        recv_fee: vec![],
+        ack_fee: coins(1000, "untrn"),
+        timeout_fee: coins(1000, "untrn"),
+    }
+}
+
+fn forwarder_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    Box::new(
+        ContractWrapper::new(execute, instantiate, query)
+            .with_sudo(sudo)
+            .with_reply_empty(reply)
+            .with_migrate_empty(migrate),
+    )
+}
+
+fn deposit_instantiate(
+    _deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response<NeutronMsg>> {
+    Ok(Response::default())
+}
+
+fn deposit_query(_deps: Deps<NeutronQuery>, env: Env, _msg: DepositQueryMsg) -> StdResult<Binary> {
+    let deposit_address: DepositAddressResponse = Some(env.contract.address.to_string());
+    to_binary(&deposit_address)
+}
+
+/// next contract that is always ready to receive funds
+fn deposit_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    Box::new(ContractWrapper::new(
+        deposit_instantiate,
+        deposit_instantiate,
+        deposit_query,
+    ))
+}
+
+struct Suite {
+    app: NeutronApp,
+    owner: Addr,
+    forwarder: Addr,
+}
+
+impl Suite {
+    fn new(reserves: Option<Vec<Coin>>) -> Suite {
+        Suite::with_config(|msg| msg.reserves = reserves)
+    }
+
+    /// forwarder of uatom with the default config, adjusted by `update`
+    fn with_config(update: impl FnOnce(&mut InstantiateMsg)) -> Suite {
+        let owner = Addr::unchecked("owner");
+        let mut app = neutron_app(NeutronMock {
+            min_ibc_fee: ibc_fee(),
+            connection_id: CONNECTION_ID.to_string(),
+            ica_channel_id: ICA_CHANNEL_ID.to_string(),
+            ica_address: ICA_ADDRESS.to_string(),
+            query_id: BALANCE_QUERY_ID,
+        });
+
+        let deposit_code_id = app.store_code(deposit_contract());
+        let next_contract = app
+            .instantiate_contract(
+                deposit_code_id,
+                owner.clone(),
+                &Empty {},
+                &[],
+                "splitter",
+                None,
+            )
+            .unwrap();
+
+        let mut msg = InstantiateMsg {
+            next_contract: next_contract.to_string(),
+            remote_chain_connection_id: CONNECTION_ID.to_string(),
+            remote_chain_channel_id: TRANSFER_CHANNEL_ID.to_string(),
+            denoms: vec![REMOTE_DENOM.to_string()],
+            ibc_transfer_timeout: 600u64.into(),
+            ica_timeout: 300u64.into(),
+            icq_update_period: 5u64.into(),
+            reserves: None,
+            forwarding_mode: None,
+            ibc_fee: None,
+            owner: Some(owner.to_string()),
+            retry_policy: None,
+            guardian: None,
+            keeper_tip: None,
+            cron_schedule: None,
+        };
+        update(&mut msg);
+
+        let forwarder_code_id = app.store_code(forwarder_contract());
+        let forwarder = app
+            .instantiate_contract(
+                forwarder_code_id,
+                owner.clone(),
+                &msg,
+                &[],
+                "forwarder",
+                Some(owner.to_string()),
+            )
+            .unwrap();
+
+        // the forwarder pays the fees of its ICA txs
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &forwarder, vec![coin(100_000, "untrn")])
+        })
+        .unwrap();
+
+        Suite {
+            app,
+            owner,
+            forwarder,
+        }
+    }
+
+    fn execute(&mut self, msg: ExecuteMsg) -> AnyResult<AppResponse> {
+        self.app
+            .execute_contract(self.owner.clone(), self.forwarder.clone(), &msg, &[])
+    }
+
+    fn tick(&mut self) -> AnyResult<AppResponse> {
+        self.execute(ExecuteMsg::Tick {})
+    }
+
+    /// registers the ICA and acknowledges the opened channel
+    fn open_ica(&mut self) {
+        self.tick().unwrap();
+        self.ack_ica_channel();
+        assert_eq!(self.contract_state(), ContractState::IcaCreated);
+    }
+
+    /// acknowledges the opened ICA channel of the last registration
+    fn ack_ica_channel(&mut self) {
+        let version = OpenAckVersion {
+            version: "ics27-1".to_string(),
+            controller_connection_id: CONNECTION_ID.to_string(),
+            host_connection_id: "connection-1".to_string(),
+            address: ICA_ADDRESS.to_string(),
+            encoding: "proto3".to_string(),
+            tx_type: "sdk_multi_msg".to_string(),
+        };
+        self.app
+            .wasm_sudo(
+                self.forwarder.clone(),
+                &SudoMsg::OpenAck {
+                    port_id: get_port_id(self.forwarder.as_str(), "ica"),
+                    channel_id: ICA_CHANNEL_ID.to_string(),
+                    counterparty_channel_id: "channel-100".to_string(),
+                    counterparty_version: serde_json_wasm::to_string(&version).unwrap(),
+                },
+            )
+            .unwrap();
+    }
+
+    /// submits a new result of the ICA balance query
+    fn set_ica_balances(&mut self, height: u64, balances: Vec<Coin>) {
+        set_icq_balances(&mut self.app, height, balances);
+    }
+
+    /// packet of the last ICA tx submitted by the forwarder
+    fn last_tx_request(&self) -> RequestPacket {
+        RequestPacket {
+            sequence: Some(last_ica_sequence(&self.app)),
+            source_port: Some(get_port_id(self.forwarder.as_str(), "ica")),
+            source_channel: Some(ICA_CHANNEL_ID.to_string()),
+            destination_port: Some("icahost".to_string()),
+            destination_channel: Some("channel-100".to_string()),
+            data: None,
+            timeout_height: None,
+            timeout_timestamp: None,
+        }
+    }
+
+    /// acknowledges the last ICA tx submitted by the forwarder
+    fn ack_last_tx(&mut self) {
+        let msg = SudoMsg::Response {
+            request: self.last_tx_request(),
+            data: Binary::default(),
+        };
+        self.app.wasm_sudo(self.forwarder.clone(), &msg).unwrap();
+    }
+
+    /// times out the last ICA tx, which closes the ICA channel
+    fn timeout_last_tx(&mut self) {
+        let msg = SudoMsg::Timeout {
+            request: self.last_tx_request(),
+        };
+        self.app.wasm_sudo(self.forwarder.clone(), &msg).unwrap();
+    }
+
+    fn contract_state(&self) -> ContractState {
+        self.app
+            .wrap()
+            .query_wasm_smart::<Option<ContractState>>(&self.forwarder, &QueryMsg::ContractState {})
+            .unwrap()
+            .unwrap()
+    }
+}
+
+/// value of the first attribute with the given key in the response
+fn attribute(response: &AppResponse, key: &str) -> Option<String> {
+    response
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.to_string())
+}
+
+/// coins the tick submitted for forwarding, as reported in its attributes
+fn forwarded_coins(response: &AppResponse) -> Option<String> {
+    attribute(response, "coins")
+}
@@ -0,0 +1,93 @@
+use cosmwasm_std::coins;
+use guild_interfaces::deposit::DepositAddressResponse;
+
+use crate::msg::{ChannelHealth, ContractState, QueryMsg};
+
+use super::{attribute, forwarded_coins, Suite, ICA_ADDRESS, ICA_CHANNEL_ID, REMOTE_DENOM};
+
+fn channel_health(suite: &Suite) -> ChannelHealth {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.forwarder, &QueryMsg::ChannelHealth {})
+        .unwrap()
+}
+
+#[test]
+fn timed_out_channel_is_recovered() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+    assert_eq!(
+        channel_health(&suite),
+        ChannelHealth {
+            channel_id: Some(ICA_CHANNEL_ID.to_string()),
+            ..ChannelHealth::default()
+        }
+    );
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    suite.tick().unwrap();
+    suite.timeout_last_tx();
+    let timed_out_at = suite.app.block_info().time;
+    assert_eq!(suite.contract_state(), ContractState::IcaRecovering);
+    assert_eq!(
+        channel_health(&suite),
+        ChannelHealth {
+            channel_id: Some(ICA_CHANNEL_ID.to_string()),
+            recovering: true,
+            timeouts: 1,
+            last_timeout: Some(timed_out_at),
+            recoveries: 0,
+            last_recovery: None,
+        }
+    );
+
+    // the ICA is re-registered, and the registration is not re-sent
+    // until it expires after `ica_timeout`
+    let response = suite.tick().unwrap();
+    assert_eq!(
+        attribute(&response, "method"),
+        Some("try_recover_ica".to_string())
+    );
+    let response = suite.tick().unwrap();
+    assert_eq!(
+        attribute(&response, "contract_state"),
+        Some("ica_registration_pending".to_string())
+    );
+
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(300);
+    });
+    let response = suite.tick().unwrap();
+    assert_eq!(
+        attribute(&response, "method"),
+        Some("try_recover_ica".to_string())
+    );
+
+    suite.ack_ica_channel();
+    let recovered_at = suite.app.block_info().time;
+    assert_eq!(suite.contract_state(), ContractState::IcaCreated);
+    assert_eq!(
+        channel_health(&suite),
+        ChannelHealth {
+            channel_id: Some(ICA_CHANNEL_ID.to_string()),
+            recovering: false,
+            timeouts: 1,
+            last_timeout: Some(timed_out_at),
+            recoveries: 1,
+            last_recovery: Some(recovered_at),
+        }
+    );
+
+    // the recovered channel keeps the ICA and forwards its funds again
+    let deposit_address: DepositAddressResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.forwarder, &QueryMsg::DepositAddress {})
+        .unwrap();
+    assert_eq!(deposit_address, Some(ICA_ADDRESS.to_string()));
+
+    suite.set_ica_balances(15, coins(1000, REMOTE_DENOM));
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("1000uatom".to_string()));
+}
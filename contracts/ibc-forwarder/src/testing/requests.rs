@@ -0,0 +1,102 @@
+use cosmwasm_std::{coins, testing::MockStorage};
+use neutron_sdk::sudo::msg::SudoMsg;
+
+use crate::{
+    contract::{read_reply_payload, save_reply_payload, SUDO_PAYLOAD_REPLY_ID_START},
+    msg::{ContractState, PendingRequest, QueryMsg, SudoPayload},
+    state::REPLY_PAYLOADS,
+};
+
+use super::{Suite, ICA_CHANNEL_ID, REMOTE_DENOM};
+
+fn in_flight_requests(suite: &Suite) -> Vec<PendingRequest> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.forwarder,
+            &QueryMsg::InFlightRequests {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+}
+
+/// whether the forwarder still holds the payload saved under the reply id
+fn has_reply_payload(suite: &Suite, reply_id: u64) -> bool {
+    suite
+        .app
+        .wrap()
+        .query_wasm_raw(&suite.forwarder, REPLY_PAYLOADS.key(reply_id).to_vec())
+        .unwrap()
+        .is_some()
+}
+
+/// submits a forward of the ICA balance and checks it is in flight
+fn forward(suite: &mut Suite, height: u64, reply_id: u64, seq_id: u64) {
+    suite.set_ica_balances(height, coins(1000, REMOTE_DENOM));
+    suite.tick().unwrap();
+
+    // the reply moved the payload to the request of the submitted tx
+    assert!(!has_reply_payload(suite, reply_id));
+    let requests = in_flight_requests(suite);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].channel_id, ICA_CHANNEL_ID);
+    assert_eq!(requests[0].seq_id, seq_id);
+    assert_eq!(requests[0].payload.message, "try_forward_funds");
+    assert_eq!(requests[0].payload.coins, coins(1000, REMOTE_DENOM));
+    assert_eq!(requests[0].submitted_at, suite.app.block_info().time);
+}
+
+#[test]
+fn reply_ids_start_after_the_reserved_ids() {
+    let mut storage = MockStorage::new();
+    let payload = SudoPayload {
+        message: "try_forward_funds".to_string(),
+        port_id: "icacontroller-forwarder.ica".to_string(),
+        coins: vec![],
+        ibc_fee: None,
+    };
+
+    assert_eq!(SUDO_PAYLOAD_REPLY_ID_START, 1000);
+    assert_eq!(
+        save_reply_payload(&mut storage, payload.clone()).unwrap(),
+        1000
+    );
+    assert_eq!(
+        save_reply_payload(&mut storage, payload.clone()).unwrap(),
+        1001
+    );
+
+    assert_eq!(read_reply_payload(&mut storage, 1000).unwrap(), payload);
+    assert!(REPLY_PAYLOADS.may_load(&storage, 1000).unwrap().is_none());
+    assert!(REPLY_PAYLOADS.has(&storage, 1001));
+}
+
+#[test]
+fn callbacks_clean_up_in_flight_requests() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+    assert!(in_flight_requests(&suite).is_empty());
+
+    forward(&mut suite, 10, 1000, 1);
+    suite.ack_last_tx();
+    assert!(in_flight_requests(&suite).is_empty());
+
+    forward(&mut suite, 15, 1001, 2);
+    let msg = SudoMsg::Error {
+        request: suite.last_tx_request(),
+        details: "insufficient funds".to_string(),
+    };
+    suite.app.wasm_sudo(suite.forwarder.clone(), &msg).unwrap();
+    assert!(in_flight_requests(&suite).is_empty());
+    assert_eq!(suite.contract_state(), ContractState::IcaCreated);
+
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(60);
+    });
+    forward(&mut suite, 20, 1002, 3);
+    suite.timeout_last_tx();
+    assert!(in_flight_requests(&suite).is_empty());
+}
@@ -0,0 +1,66 @@
+use cosmwasm_std::coins;
+
+use crate::msg::{ContractState, FailedRequest, QueryMsg, RetryStatus};
+
+use super::{forwarded_coins, Suite, ICA_CHANNEL_ID, REMOTE_DENOM};
+
+fn retry_status(suite: &Suite) -> RetryStatus {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.forwarder, &QueryMsg::RetryStatus {})
+        .unwrap()
+}
+
+fn failed_requests(suite: &Suite) -> Vec<FailedRequest> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.forwarder,
+            &QueryMsg::FailedRequests {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn timed_out_forward_backs_off() {
+    let mut suite = Suite::new(None);
+    suite.open_ica();
+
+    suite.set_ica_balances(10, coins(1000, REMOTE_DENOM));
+    suite.tick().unwrap();
+    suite.timeout_last_tx();
+    assert_eq!(suite.contract_state(), ContractState::IcaRecovering);
+
+    let timed_out_at = suite.app.block_info().time;
+    let failed = failed_requests(&suite);
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].channel_id, ICA_CHANNEL_ID);
+    assert_eq!(failed[0].error, "timeout");
+    assert_eq!(failed[0].payload.coins, coins(1000, REMOTE_DENOM));
+
+    let status = retry_status(&suite);
+    assert_eq!(status.consecutive_failures, 1);
+    assert_eq!(status.next_attempt, Some(timed_out_at.plus_seconds(60)));
+
+    // the channel is recovered right away, forwarding waits for the backoff
+    suite.open_ica();
+    suite.set_ica_balances(15, coins(1000, REMOTE_DENOM));
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), None);
+    assert_eq!(suite.contract_state(), ContractState::IcaCreated);
+
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(60);
+    });
+    let response = suite.tick().unwrap();
+    assert_eq!(forwarded_coins(&response), Some("1000uatom".to_string()));
+
+    suite.ack_last_tx();
+    assert!(failed_requests(&suite).is_empty());
+    assert_eq!(retry_status(&suite).consecutive_failures, 0);
+}
@@ -1,76 +1,606 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
-    StdResult,
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, Uint64,
 };
 use cw2::set_contract_version;
+use cw20::{BalanceResponse, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
+use guild_interfaces::{
+    admin::{
+        assert_migration, assert_not_paused, assert_owner, is_paused, query_ownership,
+        try_accept_ownership, try_set_paused, try_transfer_ownership, try_update_guardian,
+        GUARDIAN, OWNER,
+    },
+    cron::{ContractSudoMsg, CronSudoMsg},
+    deposit::DepositAddressResponse,
+    fee::{ibc_fee_or_min, query_min_ibc_fee, validate_ibc_fee},
+    keeper::{is_tip_due, LAST_PAID_TICK},
+};
+use neutron_sdk::{
+    bindings::{
+        msg::{IbcFee, MsgIbcTransferResponse, NeutronMsg},
+        query::NeutronQuery,
+    },
+    sudo::msg::{RequestPacket, RequestPacketTimeoutHeight, SudoMsg},
+};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SplitConfig, SplitType};
-use crate::state::{FALLBACK_SPLIT, SPLIT_CONFIG_MAP};
+use crate::msg::{
+    cw20_denom, get_ibc_denom, get_transfer_msg, Cw20Split, DenomDistribution, DenomSplit,
+    DistributionMode, DistributionRecord, Entitlement, ExecuteMsg, FailedSwap, IbcTransferInfo,
+    InstantiateMsg, KeeperTip, KeeperTipResponse, Member, MigrateMsg, QueryMsg, Receiver,
+    RemainderPolicy, SplitConfig, SplitType, SwapConfig, CW20_DENOM_PREFIX, MAX_MEMBER_WEIGHT,
+};
+use crate::state::{
+    LegacySplitConfig, MemberInactivity, CARRIED_DUST, CLAIMABLE, CRON_SCHEDULE,
+    CW20_SPLIT_CONFIG_MAP, DENOM_ALIASES, DENOM_TOTALS, DISTRIBUTION_HISTORY, DISTRIBUTION_MODE,
+    FAILED_SWAPS, FALLBACK_MIN_DISTRIBUTION, FALLBACK_SPLIT, IBC_FEE, KEEPER_TIP,
+    LEGACY_FALLBACK_SPLIT, LEGACY_SPLIT_CONFIG_MAP, MEMBERS, MEMBER_INACTIVITY, MIN_DISTRIBUTIONS,
+    NEXT_DISTRIBUTION_ID, NEXT_REPLY_ID, PENDING_IBC_TRANSFERS, RECEIVER_TOTALS, REMAINDER_POLICY,
+    REPLY_PAYLOADS, SPLIT_CONFIG_MAP, SWAP_CONFIG, TOTAL_CLAIMABLE,
+};
 
 const CONTRACT_NAME: &str = "crates.io:protocol-guild-splitter";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const TRANSFER_PORT_ID: &str = "transfer";
+const DEFAULT_HISTORY_LIMIT: u32 = 10;
+const MAX_HISTORY_LIMIT: u32 = 30;
+
+type QueryDeps<'a> = Deps<'a, NeutronQuery>;
+type ExecuteDeps<'a> = DepsMut<'a, NeutronQuery>;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: ExecuteDeps,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
+) -> Result<Response<NeutronMsg>, ContractError> {
     deps.api.debug("WASMDEBUG: instantiate");
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     // we validate the splits and store them per-denom in a map
     for denom_split in msg.splits {
-        let validated_split = match denom_split.split {
-            SplitType::Custom(c) => c.validate()?,
-        };
-        SPLIT_CONFIG_MAP.save(deps.storage, denom_split.denom, &validated_split)?;
+        save_denom_split(deps.storage, denom_split)?;
     }
 
     // if a fallback split is provided we validate and store it
     if let Some(split) = msg.fallback_split {
-        FALLBACK_SPLIT.save(deps.storage, &split.get_split_config()?.validate()?)?;
+        FALLBACK_SPLIT.save(deps.storage, &split.validate()?)?;
+        if let Some(min_distribution) = msg.fallback_min_distribution {
+            FALLBACK_MIN_DISTRIBUTION.save(deps.storage, &min_distribution)?;
+        }
+    }
+
+    for member in msg.members {
+        save_member(deps.branch(), &env, member)?;
+    }
+
+    // cw20 tokens are only accepted and distributed if they have a split
+    for cw20_split in msg.cw20_splits {
+        save_cw20_split(deps.branch(), cw20_split)?;
+    }
+
+    let ibc_fee = ibc_fee_or_min(deps.as_ref(), msg.ibc_fee)?;
+    IBC_FEE.save(deps.storage, &ibc_fee)?;
+
+    DISTRIBUTION_MODE.save(
+        deps.storage,
+        &msg.distribution_mode.unwrap_or(DistributionMode::Push),
+    )?;
+
+    let remainder_policy = validate_remainder_policy(
+        deps.as_ref(),
+        msg.remainder_policy
+            .unwrap_or(RemainderPolicy::CarryForward),
+    )?;
+    REMAINDER_POLICY.save(deps.storage, &remainder_policy)?;
+
+    if let Some(guardian) = msg.guardian {
+        GUARDIAN.save(deps.storage, &deps.api.addr_validate(&guardian)?)?;
+    }
+
+    if let Some(keeper_tip) = msg.keeper_tip {
+        keeper_tip.validate()?;
+        KEEPER_TIP.save(deps.storage, &keeper_tip)?;
+    }
+
+    if let Some(schedule) = msg.cron_schedule {
+        CRON_SCHEDULE.save(deps.storage, &schedule)?;
+    }
+
+    if let Some(swap_config) = msg.swap_config {
+        swap_config.validate()?;
+        SWAP_CONFIG.save(deps.storage, &swap_config)?;
+    }
+
+    // without an owner the split configuration can never be updated
+    let owner = match msg.owner {
+        Some(addr) => {
+            let owner = deps.api.addr_validate(&addr)?;
+            OWNER.save(deps.storage, &owner)?;
+            owner.to_string()
+        }
+        None => "none".to_string(),
+    };
+
+    Ok(Response::default()
+        .add_attribute("method", "protocol_guild_splitter_instantiate")
+        .add_attribute("owner", owner))
+}
+
+fn save_denom_split(
+    storage: &mut dyn Storage,
+    denom_split: DenomSplit,
+) -> Result<(), ContractError> {
+    let validated_split = denom_split.split.validate()?;
+
+    // balances are held under the hashed denom, so that is what
+    // splits given by their trace are stored under. a split given by
+    // the hashed denom replaces the one set by its trace.
+    let denom = match get_ibc_denom(&denom_split.denom) {
+        Some(ibc_denom) => {
+            DENOM_ALIASES.save(storage, denom_split.denom, &ibc_denom)?;
+            ibc_denom
+        }
+        None => {
+            remove_denom_aliases(storage, &denom_split.denom)?;
+            denom_split.denom
+        }
+    };
+
+    SPLIT_CONFIG_MAP.save(storage, denom.to_string(), &validated_split)?;
+    match denom_split.min_distribution {
+        Some(min_distribution) => MIN_DISTRIBUTIONS.save(storage, denom, &min_distribution)?,
+        None => MIN_DISTRIBUTIONS.remove(storage, denom),
     }
+    Ok(())
+}
 
-    Ok(Response::default().add_attribute("method", "protocol_guild_splitter_instantiate"))
+/// removes the traces resolving to `denom`, so that no alias outlives
+/// the split it was set for
+fn remove_denom_aliases(storage: &mut dyn Storage, denom: &str) -> StdResult<()> {
+    let mut aliases: Vec<String> = vec![];
+    for entry in DENOM_ALIASES.range(storage, None, None, Order::Ascending) {
+        let (alias, resolved) = entry?;
+        if resolved == denom {
+            aliases.push(alias);
+        }
+    }
+    for alias in aliases {
+        DENOM_ALIASES.remove(storage, alias);
+    }
+    Ok(())
+}
+
+fn save_cw20_split(deps: ExecuteDeps, cw20_split: Cw20Split) -> Result<(), ContractError> {
+    let token = deps.api.addr_validate(&cw20_split.token)?;
+    let validated_split = cw20_split.split.validate()?;
+    CW20_SPLIT_CONFIG_MAP.save(deps.storage, token, &validated_split)?;
+    Ok(())
+}
+
+fn validate_remainder_policy(
+    deps: QueryDeps,
+    policy: RemainderPolicy,
+) -> Result<RemainderPolicy, ContractError> {
+    match policy {
+        RemainderPolicy::Receiver(addr) => Ok(RemainderPolicy::Receiver(
+            deps.api.addr_validate(&addr)?.to_string(),
+        )),
+        policy => Ok(policy),
+    }
+}
+
+fn save_member(deps: ExecuteDeps, env: &Env, member: Member) -> Result<(), ContractError> {
+    let addr = deps.api.addr_validate(&member.addr)?;
+    if member.weight.is_zero() || member.weight > Uint128::new(MAX_MEMBER_WEIGHT) {
+        return Err(ContractError::MemberMisconfig {});
+    }
+
+    // a new start begins a new tenure, forgetting the past inactive periods
+    let mut inactivity = match MEMBERS.may_load(deps.storage, addr.clone())? {
+        Some(previous) if previous.start == member.start => MEMBER_INACTIVITY
+            .may_load(deps.storage, addr.clone())?
+            .unwrap_or_default(),
+        _ => MemberInactivity::default(),
+    };
+    match (inactivity.inactive_since, member.active) {
+        // time before the start does not count anyway
+        (None, false) => {
+            inactivity.inactive_since = Some(std::cmp::max(env.block.time, member.start))
+        }
+        (Some(since), true) => {
+            inactivity.inactive_since = None;
+            inactivity.inactive_seconds += env.block.time.seconds().saturating_sub(since.seconds());
+        }
+        _ => {}
+    }
+
+    MEMBERS.save(deps.storage, addr.clone(), &member)?;
+    MEMBER_INACTIVITY.save(deps.storage, addr, &inactivity)?;
+    Ok(())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    deps: ExecuteDeps,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: ExecuteMsg,
-) -> Result<Response, ContractError> {
+) -> Result<Response<NeutronMsg>, ContractError> {
     deps.api
         .debug(format!("WASMDEBUG: execute: received msg: {msg:?}").as_str());
 
     match msg {
-        ExecuteMsg::Tick {} => try_distribute(deps, env),
+        ExecuteMsg::Tick {} => try_distribute(deps, env, Some(info.sender)),
+        ExecuteMsg::UpdateSplits { set, remove } => try_update_splits(deps, info, set, remove),
+        ExecuteMsg::UpdateFallback {
+            split,
+            min_distribution,
+        } => try_update_fallback(deps, info, split, min_distribution),
+        ExecuteMsg::TransferOwnership { new_owner } => {
+            Ok(try_transfer_ownership(deps, info, new_owner)?)
+        }
+        ExecuteMsg::AcceptOwnership {} => Ok(try_accept_ownership(deps, info)?),
+        ExecuteMsg::UpdateMembers { set, remove } => {
+            try_update_members(deps, env, info, set, remove)
+        }
+        ExecuteMsg::UpdateDistributionMode { mode } => {
+            try_update_distribution_mode(deps, info, mode)
+        }
+        ExecuteMsg::UpdateRemainderPolicy { policy } => {
+            try_update_remainder_policy(deps, info, policy)
+        }
+        ExecuteMsg::Claim { denoms } => try_claim(deps, info, denoms),
+        ExecuteMsg::Receive(receive_msg) => try_receive_cw20(deps, info, receive_msg),
+        ExecuteMsg::UpdateCw20Splits { set, remove } => {
+            try_update_cw20_splits(deps, info, set, remove)
+        }
+        ExecuteMsg::Pause {} => Ok(try_set_paused(deps, info, true)?),
+        ExecuteMsg::Unpause {} => Ok(try_set_paused(deps, info, false)?),
+        ExecuteMsg::UpdateGuardian { guardian } => Ok(try_update_guardian(deps, info, guardian)?),
+        ExecuteMsg::EmergencyWithdraw { recovery_address } => {
+            try_emergency_withdraw(deps, env, info, recovery_address)
+        }
+        ExecuteMsg::UpdateKeeperTip { keeper_tip } => try_update_keeper_tip(deps, info, keeper_tip),
+        ExecuteMsg::UpdateCronSchedule { schedule } => {
+            try_update_cron_schedule(deps, info, schedule)
+        }
+        ExecuteMsg::UpdateSwapConfig { swap_config } => {
+            try_update_swap_config(deps, info, swap_config)
+        }
+        ExecuteMsg::UpdateIbcFee { ibc_fee } => try_update_ibc_fee(deps, info, ibc_fee),
+    }
+}
+
+fn try_update_keeper_tip(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    keeper_tip: Option<KeeperTip>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match keeper_tip {
+        Some(keeper_tip) => {
+            keeper_tip.validate()?;
+            KEEPER_TIP.save(deps.storage, &keeper_tip)?;
+        }
+        None => KEEPER_TIP.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_keeper_tip"))
+}
+
+fn try_update_cron_schedule(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    schedule: Option<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match schedule {
+        Some(schedule) => CRON_SCHEDULE.save(deps.storage, &schedule)?,
+        None => CRON_SCHEDULE.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_cron_schedule"))
+}
+
+fn try_update_swap_config(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    swap_config: Option<SwapConfig>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match swap_config {
+        Some(swap_config) => {
+            swap_config.validate()?;
+            SWAP_CONFIG.save(deps.storage, &swap_config)?;
+        }
+        None => SWAP_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_swap_config"))
+}
+
+fn try_update_ibc_fee(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    ibc_fee: IbcFee,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let ibc_fee = validate_ibc_fee(deps.as_ref(), ibc_fee)?;
+    IBC_FEE.save(deps.storage, &ibc_fee)?;
+
+    Ok(Response::default().add_attribute("method", "try_update_ibc_fee"))
+}
+
+fn try_emergency_withdraw(
+    deps: ExecuteDeps,
+    env: Env,
+    info: MessageInfo,
+    recovery_address: String,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    let recovery_address = deps.api.addr_validate(&recovery_address)?;
+
+    // balances owed to receivers stay claimable, only the rest is withdrawn
+    let mut balances = query_distributable_balances(deps.as_ref(), &env)?;
+    let tokens = CW20_SPLIT_CONFIG_MAP
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for token in tokens {
+        balances.push(query_distributable_cw20_balance(
+            deps.as_ref(),
+            &env,
+            &token,
+        )?);
+    }
+
+    let mut withdraw_messages: Vec<CosmosMsg<NeutronMsg>> = vec![];
+    for coin in balances.into_iter().filter(|c| !c.amount.is_zero()) {
+        withdraw_messages.push(get_transfer_msg(
+            recovery_address.to_string(),
+            &coin.denom,
+            coin.amount,
+        )?);
+    }
+    if withdraw_messages.is_empty() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+
+    // carried dust is withdrawn along with the rest of the balances.
+    // claimable balances and the failed swaps they were credited for are
+    // kept. transfers in flight are forgotten, their refunds are not
+    // credited to anyone and can be withdrawn again. reply payloads only
+    // live within a single tx, but are dropped too so that no stale
+    // transfer can be recorded later on.
+    CARRIED_DUST.clear(deps.storage);
+    PENDING_IBC_TRANSFERS.clear(deps.storage);
+    REPLY_PAYLOADS.clear(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("method", "try_emergency_withdraw")
+        .add_attribute("recovery_address", recovery_address)
+        .add_messages(withdraw_messages))
+}
+
+fn try_update_splits(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    set: Vec<DenomSplit>,
+    remove: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    // splits are removed by their trace or by the hashed denom
+    for denom in remove {
+        let denom = get_ibc_denom(&denom).unwrap_or(denom);
+        remove_denom_aliases(deps.storage, &denom)?;
+        SPLIT_CONFIG_MAP.remove(deps.storage, denom.to_string());
+        MIN_DISTRIBUTIONS.remove(deps.storage, denom);
+    }
+
+    // new splits are validated the same way as on instantiation and
+    // overwrite any existing split of the same denom
+    for denom_split in set {
+        save_denom_split(deps.storage, denom_split)?;
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_splits"))
+}
+
+fn try_update_fallback(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    split: Option<SplitType>,
+    min_distribution: Option<Uint128>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match split {
+        Some(split) => FALLBACK_SPLIT.save(deps.storage, &split.validate()?)?,
+        None => FALLBACK_SPLIT.remove(deps.storage),
+    }
+    match min_distribution {
+        Some(min_distribution) => {
+            FALLBACK_MIN_DISTRIBUTION.save(deps.storage, &min_distribution)?
+        }
+        None => FALLBACK_MIN_DISTRIBUTION.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_fallback"))
+}
+
+fn try_update_cw20_splits(
+    mut deps: ExecuteDeps,
+    info: MessageInfo,
+    set: Vec<Cw20Split>,
+    remove: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    for token in remove {
+        let token = deps.api.addr_validate(&token)?;
+        CW20_SPLIT_CONFIG_MAP.remove(deps.storage, token);
+    }
+
+    for cw20_split in set {
+        save_cw20_split(deps.branch(), cw20_split)?;
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_cw20_splits"))
+}
+
+/// cw20 tokens sent to the contract are held until the next tick.
+/// we only reject tokens that would never be distributed.
+fn try_receive_cw20(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    if !CW20_SPLIT_CONFIG_MAP.has(deps.storage, info.sender.clone()) {
+        return Err(ContractError::Cw20NotAllowed {});
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "try_receive_cw20")
+        .add_attribute("token", info.sender)
+        .add_attribute("sender", receive_msg.sender)
+        .add_attribute("amount", receive_msg.amount))
+}
+
+fn try_update_members(
+    mut deps: ExecuteDeps,
+    env: Env,
+    info: MessageInfo,
+    set: Vec<Member>,
+    remove: Vec<String>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    for addr in remove {
+        let addr = deps.api.addr_validate(&addr)?;
+        MEMBERS.remove(deps.storage, addr.clone());
+        MEMBER_INACTIVITY.remove(deps.storage, addr);
     }
+
+    for member in set {
+        save_member(deps.branch(), &env, member)?;
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_members"))
 }
 
-pub fn try_distribute(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
-    // first we query the contract balances
-    let mut balances = deps.querier.query_all_balances(env.contract.address)?;
-    let mut distribution_messages: Vec<CosmosMsg> = vec![];
+fn try_update_distribution_mode(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    mode: DistributionMode,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    // switching back to push mode does not touch the accrued balances,
+    // receivers can still claim them
+    DISTRIBUTION_MODE.save(deps.storage, &mode)?;
+
+    Ok(Response::default().add_attribute("method", "try_update_distribution_mode"))
+}
+
+fn try_update_remainder_policy(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    policy: RemainderPolicy,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let policy = validate_remainder_policy(deps.as_ref(), policy)?;
+    REMAINDER_POLICY.save(deps.storage, &policy)?;
+
+    Ok(Response::default().add_attribute("method", "try_update_remainder_policy"))
+}
+
+fn try_claim(
+    deps: ExecuteDeps,
+    info: MessageInfo,
+    denoms: Option<Vec<String>>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    let claimer = info.sender.to_string();
+
+    let denoms = match denoms {
+        Some(denoms) => denoms,
+        None => CLAIMABLE
+            .prefix(claimer.to_string())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<String>>>()?,
+    };
+
+    let mut claim_messages: Vec<CosmosMsg<NeutronMsg>> = vec![];
+    for denom in denoms {
+        let key = (claimer.to_string(), denom.to_string());
+        let amount = CLAIMABLE
+            .may_load(deps.storage, key.clone())?
+            .unwrap_or_default();
+        if amount.is_zero() {
+            continue;
+        }
+
+        CLAIMABLE.remove(deps.storage, key);
+        TOTAL_CLAIMABLE.update(deps.storage, denom.to_string(), |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_sub(amount)?)
+        })?;
+        claim_messages.push(get_transfer_msg(claimer.to_string(), &denom, amount)?);
+    }
+
+    if claim_messages.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    Ok(Response::default()
+        .add_attribute("method", "try_claim")
+        .add_attribute("claimer", claimer)
+        .add_messages(claim_messages))
+}
+
+/// distributes the pending balances. the keeper, if any, gets tipped
+/// out of the distributed amounts.
+pub fn try_distribute(
+    mut deps: ExecuteDeps,
+    env: Env,
+    keeper: Option<Addr>,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    assert_not_paused(deps.storage)?;
+
+    // first we query the contract balances that are up for distribution
+    let mut balances = query_distributable_balances(deps.as_ref(), &env)?;
+    let distributable = balances.clone();
+    // (coin, split config) pairs to be distributed during this tick
+    let mut pending_splits: Vec<(Coin, SplitConfig)> = vec![];
+
+    // all time weighted splits share the same member registry, so
+    // we only compute the current member shares once per tick
+    let time_weighted_config = get_time_weighted_config(deps.as_ref(), &env)?;
 
     // then we iterate over our split config and try to match the entries to available balances
     for entry in SPLIT_CONFIG_MAP.range(deps.storage, None, None, Order::Ascending) {
-        let (denom, config) = entry?;
+        let (denom, split) = entry?;
 
         // we try to find the index of matching coin in available balances
         let balances_index = balances.iter().position(|coin| coin.denom == denom);
         if let Some(index) = balances_index {
-            // pop the relevant coin and build the transfer messages
+            // pop the relevant coin and match it with its split. balances
+            // below the minimum distribution wait for a later tick.
             let coin = balances.remove(index);
-            let mut transfer_messages =
-                config.get_transfer_messages(coin.amount, coin.denom.to_string())?;
-            distribution_messages.append(&mut transfer_messages);
+            let min_distribution = MIN_DISTRIBUTIONS
+                .may_load(deps.storage, denom)?
+                .unwrap_or_default();
+            if coin.amount >= min_distribution {
+                pending_splits.push((coin, resolve_split_config(split, &time_weighted_config)));
+            }
         }
     }
 
@@ -80,47 +610,892 @@ pub fn try_distribute(deps: DepsMut, env: Env) -> Result<Response, ContractError
     // if its not provided, this contract will continue to hold those tokens
     // until its provided
     if let Some(split) = FALLBACK_SPLIT.may_load(deps.storage)? {
-        // get the distribution messages and add them to the list
+        let config = resolve_split_config(split, &time_weighted_config);
+        let min_distribution = FALLBACK_MIN_DISTRIBUTION
+            .may_load(deps.storage)?
+            .unwrap_or_default();
         for leftover_bal in balances {
-            let mut fallback_messages =
-                split.get_transfer_messages(leftover_bal.amount, leftover_bal.denom)?;
-            distribution_messages.append(&mut fallback_messages);
+            if leftover_bal.amount >= min_distribution {
+                pending_splits.push((leftover_bal, config.clone()));
+            }
         }
     }
 
+    // cw20 tokens are not covered by the fallback split, only the
+    // allowlisted tokens are distributed according to their own split
+    for entry in CW20_SPLIT_CONFIG_MAP.range(deps.storage, None, None, Order::Ascending) {
+        let (token, split) = entry?;
+        let balance = query_distributable_cw20_balance(deps.as_ref(), &env, &token)?;
+        if !balance.amount.is_zero() {
+            pending_splits.push((balance, resolve_split_config(split, &time_weighted_config)));
+        }
+    }
+
+    let mode = load_distribution_mode(deps.storage)?;
+    let remainder_policy = load_remainder_policy(deps.storage)?;
+
+    // ibc transfers escrow their fees from the contract balance, so the
+    // fees have to be held back before the balances are split
+    let ibc_fees_covered = match mode {
+        DistributionMode::Push => reserve_ibc_fees(
+            deps.as_ref(),
+            &distributable,
+            &mut pending_splits,
+            &remainder_policy,
+        )?,
+        DistributionMode::Accrue => true,
+    };
+    let swaps_enabled = SWAP_CONFIG.may_load(deps.storage)?.is_some();
+
+    let tip_messages = match keeper {
+        Some(keeper) => take_keeper_tips(
+            deps.storage,
+            &env,
+            &keeper,
+            &mut pending_splits,
+            &remainder_policy,
+        )?,
+        None => vec![],
+    };
+
+    let mut distribution_messages: Vec<SubMsg<NeutronMsg>> = vec![];
+    let mut distributions: Vec<DenomDistribution> = vec![];
+
+    // carried dust is part of the balance split on the next tick, so we
+    // only keep track of the remainders of this tick. balances that wait
+    // for their minimum distribution carry no dust.
+    CARRIED_DUST.clear(deps.storage);
+
+    for (coin, config) in pending_splits {
+        let (entitlements, remainder) = config.get_entitlements(coin.amount, &remainder_policy)?;
+        let mut denom_distribution = DenomDistribution {
+            denom: coin.denom.to_string(),
+            total: Uint128::zero(),
+            receivers: vec![],
+        };
+
+        // bank rejects zero amount sends, so receivers whose share
+        // rounds down to nothing are left out
+        for entitlement in entitlements {
+            if entitlement.amount.is_zero() {
+                continue;
+            }
+            denom_distribution.total += entitlement.amount;
+            denom_distribution
+                .receivers
+                .push((entitlement.addr.to_string(), entitlement.amount));
+
+            // in accrue mode we credit the receiver so they can claim their
+            // split later. so do IBC receivers whose fees can not be covered,
+            // which keeps the tick from failing for the local receivers.
+            let credit = mode == DistributionMode::Accrue
+                || (!ibc_fees_covered && is_ibc_payout(&entitlement, &coin.denom, swaps_enabled));
+            if credit {
+                credit_claimable(
+                    deps.storage,
+                    entitlement.addr,
+                    coin.denom.to_string(),
+                    entitlement.amount,
+                )?;
+            } else {
+                // get the distribution message and add it to the list
+                let submsg = get_payout_submsg(deps.branch(), &env, entitlement, &coin.denom)?;
+                distribution_messages.push(submsg);
+            }
+        }
+
+        if !remainder.is_zero() {
+            CARRIED_DUST.save(deps.storage, coin.denom, &remainder)?;
+        }
+
+        if !denom_distribution.total.is_zero() {
+            distributions.push(denom_distribution);
+        }
+    }
+
+    record_distribution(deps.storage, &env, distributions)?;
+
     Ok(Response::default()
         .add_attribute("method", "try_distribute")
-        .add_messages(distribution_messages))
+        .add_attribute("ibc_fees_covered", ibc_fees_covered.to_string())
+        .add_submessages(distribution_messages)
+        .add_messages(tip_messages))
+}
+
+/// whether paying out the entitlement sends it over IBC, either to the
+/// receiver or to be swapped on osmosis. cw20 tokens are never sent over IBC.
+fn is_ibc_payout(entitlement: &Entitlement, denom: &str, swaps_enabled: bool) -> bool {
+    if denom.starts_with(CW20_DENOM_PREFIX) {
+        return false;
+    }
+    let swaps = swaps_enabled
+        && matches!(&entitlement.payout_denom, Some(payout_denom) if payout_denom != denom);
+    swaps || entitlement.ibc.is_some()
+}
+
+/// deducts `(ack_fee + timeout_fee) * ibc payouts` of this tick from the
+/// pending splits. balances that are not split this tick count towards
+/// the reserve before anything is taken from the pending splits.
+/// returns whether the balances cover the fees, nothing is deducted
+/// if they do not.
+fn reserve_ibc_fees(
+    deps: QueryDeps,
+    distributable: &[Coin],
+    pending_splits: &mut [(Coin, SplitConfig)],
+    remainder_policy: &RemainderPolicy,
+) -> Result<bool, ContractError> {
+    let swaps_enabled = SWAP_CONFIG.may_load(deps.storage)?.is_some();
+    let mut ibc_payouts: u128 = 0;
+    for (coin, config) in pending_splits.iter() {
+        if coin.denom.starts_with(CW20_DENOM_PREFIX) {
+            continue;
+        }
+        // entitlements that round down to nothing are skipped by the
+        // split, so they do not escrow any fees either
+        let (entitlements, _) = config.get_entitlements(coin.amount, remainder_policy)?;
+        for entitlement in entitlements {
+            if !entitlement.amount.is_zero()
+                && is_ibc_payout(&entitlement, &coin.denom, swaps_enabled)
+            {
+                ibc_payouts += 1;
+            }
+        }
+    }
+    if ibc_payouts == 0 {
+        return Ok(true);
+    }
+
+    let ibc_fee = IBC_FEE.load(deps.storage)?;
+    let mut reserve: Vec<Coin> = vec![];
+    for fee in ibc_fee.ack_fee.into_iter().chain(ibc_fee.timeout_fee) {
+        let amount = fee
+            .amount
+            .checked_mul(Uint128::new(ibc_payouts))
+            .map_err(StdError::from)?;
+        match reserve.iter_mut().find(|coin| coin.denom == fee.denom) {
+            Some(coin) => coin.amount = coin.amount.checked_add(amount).map_err(StdError::from)?,
+            None => reserve.push(Coin {
+                denom: fee.denom,
+                amount,
+            }),
+        }
+    }
+
+    let balance_of = |denom: &str| {
+        distributable
+            .iter()
+            .find(|coin| coin.denom == denom)
+            .map(|coin| coin.amount)
+            .unwrap_or_default()
+    };
+    if reserve
+        .iter()
+        .any(|required| balance_of(&required.denom) < required.amount)
+    {
+        return Ok(false);
+    }
+
+    for required in reserve {
+        let balance = balance_of(&required.denom);
+        if let Some((coin, _)) = pending_splits
+            .iter_mut()
+            .find(|(coin, _)| coin.denom == required.denom)
+        {
+            let held_back = balance.saturating_sub(coin.amount);
+            coin.amount -= required.amount.saturating_sub(held_back);
+        }
+    }
+
+    Ok(true)
+}
+
+/// deducts the keeper tip from the pending splits and returns the
+/// transfers paying it out. nothing is paid if the last paid tick
+/// happened less than `min_interval` seconds ago. splits that would not
+/// pay anyone, e.g. time weighted splits without members, are not
+/// tipped either.
+fn take_keeper_tips(
+    storage: &mut dyn Storage,
+    env: &Env,
+    keeper: &Addr,
+    pending_splits: &mut [(Coin, SplitConfig)],
+    remainder_policy: &RemainderPolicy,
+) -> Result<Vec<CosmosMsg<NeutronMsg>>, ContractError> {
+    let Some(keeper_tip) = KEEPER_TIP.may_load(storage)? else {
+        return Ok(vec![]);
+    };
+    if !is_tip_due(storage, env, keeper_tip.min_interval)? {
+        return Ok(vec![]);
+    }
+
+    let mut tip_messages: Vec<CosmosMsg<NeutronMsg>> = vec![];
+    for (coin, config) in pending_splits.iter_mut() {
+        let tip = coin.amount.multiply_ratio(keeper_tip.bps, 10_000u128);
+        if tip.is_zero() {
+            continue;
+        }
+        let (entitlements, _) = config.get_entitlements(coin.amount - tip, remainder_policy)?;
+        if entitlements
+            .iter()
+            .all(|entitlement| entitlement.amount.is_zero())
+        {
+            continue;
+        }
+        coin.amount -= tip;
+        tip_messages.push(get_transfer_msg(keeper.to_string(), &coin.denom, tip)?);
+    }
+
+    if !tip_messages.is_empty() {
+        LAST_PAID_TICK.save(storage, &env.block.time)?;
+    }
+
+    Ok(tip_messages)
+}
+
+/// persists the distributions of a tick and adds them to the lifetime totals
+fn record_distribution(
+    storage: &mut dyn Storage,
+    env: &Env,
+    distributions: Vec<DenomDistribution>,
+) -> StdResult<()> {
+    if distributions.is_empty() {
+        return Ok(());
+    }
+
+    for distribution in distributions.iter() {
+        DENOM_TOTALS.update(
+            storage,
+            distribution.denom.to_string(),
+            |total| -> StdResult<_> {
+                Ok(total.unwrap_or_default().checked_add(distribution.total)?)
+            },
+        )?;
+        for (addr, amount) in distribution.receivers.iter() {
+            RECEIVER_TOTALS.update(
+                storage,
+                (addr.to_string(), distribution.denom.to_string()),
+                |total| -> StdResult<_> { Ok(total.unwrap_or_default().checked_add(*amount)?) },
+            )?;
+        }
+    }
+
+    let id = NEXT_DISTRIBUTION_ID.may_load(storage)?.unwrap_or_default();
+    NEXT_DISTRIBUTION_ID.save(storage, &(id + 1))?;
+    DISTRIBUTION_HISTORY.save(
+        storage,
+        id,
+        &DistributionRecord {
+            id,
+            height: env.block.height,
+            time: env.block.time,
+            distributions,
+        },
+    )
+}
+
+/// builds the payout of an entitlement. native splits of receivers with a
+/// payout denom are swapped on osmosis, native denoms of receivers with an
+/// IBC destination are sent over IBC, everything else is sent on neutron.
+fn get_payout_submsg(
+    deps: ExecuteDeps,
+    env: &Env,
+    entitlement: Entitlement,
+    denom: &str,
+) -> Result<SubMsg<NeutronMsg>, ContractError> {
+    if denom.starts_with(CW20_DENOM_PREFIX) {
+        let msg = get_transfer_msg(entitlement.addr, denom, entitlement.amount)?;
+        return Ok(SubMsg::new(msg));
+    }
+
+    let coin = Coin {
+        denom: denom.to_string(),
+        amount: entitlement.amount,
+    };
+
+    // splits of other denoms than the payout denom are sent to the
+    // crosschain swaps contract, which forwards the output to the receiver
+    let swap_config = SWAP_CONFIG.may_load(deps.storage)?;
+    if let (Some(payout_denom), Some(swap_config)) = (&entitlement.payout_denom, swap_config) {
+        if payout_denom != denom {
+            let receiver = match &entitlement.ibc {
+                Some(ibc) => ibc.remote_addr.to_string(),
+                None => entitlement.addr.to_string(),
+            };
+            let memo = swap_config.get_swap_memo(payout_denom, &receiver)?;
+            return get_ibc_transfer_submsg(
+                deps,
+                env,
+                swap_config.channel_id,
+                swap_config.swap_contract,
+                swap_config.timeout,
+                memo,
+                IbcTransferInfo {
+                    addr: entitlement.addr,
+                    remote_addr: receiver,
+                    coin,
+                    payout_denom: Some(payout_denom.to_string()),
+                },
+            );
+        }
+    }
+
+    match entitlement.ibc {
+        Some(ibc) => get_ibc_transfer_submsg(
+            deps,
+            env,
+            ibc.channel_id,
+            ibc.remote_addr.to_string(),
+            ibc.timeout,
+            "".to_string(),
+            IbcTransferInfo {
+                addr: entitlement.addr,
+                remote_addr: ibc.remote_addr,
+                coin,
+                payout_denom: None,
+            },
+        ),
+        None => {
+            let msg = get_transfer_msg(entitlement.addr, denom, entitlement.amount)?;
+            Ok(SubMsg::new(msg))
+        }
+    }
+}
+
+/// IBC transfer of `transfer.coin` that is tracked until its acknowledgement
+fn get_ibc_transfer_submsg(
+    deps: ExecuteDeps,
+    env: &Env,
+    channel_id: String,
+    receiver: String,
+    timeout: Uint64,
+    memo: String,
+    transfer: IbcTransferInfo,
+) -> Result<SubMsg<NeutronMsg>, ContractError> {
+    let transfer_msg = NeutronMsg::IbcTransfer {
+        source_port: TRANSFER_PORT_ID.to_string(),
+        source_channel: channel_id,
+        token: transfer.coin.clone(),
+        sender: env.contract.address.to_string(),
+        receiver,
+        timeout_height: RequestPacketTimeoutHeight {
+            revision_number: None,
+            revision_height: None,
+        },
+        timeout_timestamp: env.block.time.plus_seconds(timeout.u64()).nanos(),
+        memo,
+        fee: IBC_FEE.load(deps.storage)?,
+    };
+
+    // the packet sequence is only known in the reply, so we store the
+    // transfer under a unique reply id until then
+    let reply_id = NEXT_REPLY_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_REPLY_ID.save(deps.storage, &(reply_id + 1))?;
+    REPLY_PAYLOADS.save(deps.storage, reply_id, &transfer)?;
+
+    Ok(SubMsg::reply_on_success(transfer_msg, reply_id))
+}
+
+/// returns the contract balances minus the amounts that are owed to
+/// receivers and waiting to be claimed
+fn query_distributable_balances(deps: QueryDeps, env: &Env) -> StdResult<Vec<Coin>> {
+    let mut distributable: Vec<Coin> = vec![];
+
+    for coin in deps.querier.query_all_balances(&env.contract.address)? {
+        let owed = TOTAL_CLAIMABLE
+            .may_load(deps.storage, coin.denom.to_string())?
+            .unwrap_or_default();
+        let amount = coin.amount.saturating_sub(owed);
+        if !amount.is_zero() {
+            distributable.push(Coin {
+                denom: coin.denom,
+                amount,
+            });
+        }
+    }
+
+    Ok(distributable)
+}
+
+/// returns the contract balance of a cw20 token minus the amount owed
+/// to receivers, denominated in its internal `cw20:` denom
+fn query_distributable_cw20_balance(deps: QueryDeps, env: &Env, token: &Addr) -> StdResult<Coin> {
+    let balance: BalanceResponse = deps.querier.query_wasm_smart(
+        token,
+        &Cw20QueryMsg::Balance {
+            address: env.contract.address.to_string(),
+        },
+    )?;
+
+    let denom = cw20_denom(token);
+    let owed = TOTAL_CLAIMABLE
+        .may_load(deps.storage, denom.to_string())?
+        .unwrap_or_default();
+
+    Ok(Coin {
+        denom,
+        amount: balance.balance.saturating_sub(owed),
+    })
+}
+
+fn credit_claimable(
+    storage: &mut dyn Storage,
+    addr: String,
+    denom: String,
+    amount: Uint128,
+) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+
+    CLAIMABLE.update(storage, (addr, denom.to_string()), |bal| -> StdResult<_> {
+        Ok(bal.unwrap_or_default().checked_add(amount)?)
+    })?;
+    TOTAL_CLAIMABLE.update(storage, denom, |total| -> StdResult<_> {
+        Ok(total.unwrap_or_default().checked_add(amount)?)
+    })?;
+
+    Ok(())
+}
+
+/// returns the receivers a split should pay out to
+fn resolve_split_config(split: SplitType, time_weighted_config: &SplitConfig) -> SplitConfig {
+    match split {
+        SplitType::Custom(config) => config,
+        SplitType::TimeWeighted {} => time_weighted_config.clone(),
+    }
+}
+
+/// builds a split config out of the active guild members where each
+/// receiver share is proportional to the time the member has been
+/// active (in seconds) multiplied by the member weight factor
+pub fn get_time_weighted_config(deps: QueryDeps, env: &Env) -> StdResult<SplitConfig> {
+    let mut member_shares: Vec<(Addr, Uint128)> = vec![];
+
+    let mut total_share = Uint128::zero();
+
+    for entry in MEMBERS.range(deps.storage, None, None, Order::Ascending) {
+        let (addr, member) = entry?;
+        let inactivity = MEMBER_INACTIVITY
+            .may_load(deps.storage, addr.clone())?
+            .unwrap_or_default();
+        let share = member.share_at(env.block.time, inactivity.inactive_seconds)?;
+        // members without a share are left out so that they do not
+        // receive zero amount transfers
+        if !share.is_zero() {
+            total_share = total_share.checked_add(share)?;
+            member_shares.push((addr, share));
+        }
+    }
+
+    let receivers = member_shares
+        .into_iter()
+        .map(|(addr, share)| Receiver {
+            addr: addr.to_string(),
+            share: Decimal::from_ratio(share, total_share),
+            ibc: None,
+            payout_denom: None,
+        })
+        .collect();
+
+    Ok(SplitConfig { receivers })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn reply(deps: ExecuteDeps, _env: Env, msg: Reply) -> StdResult<Response> {
+    deps.api
+        .debug(format!("WASMDEBUG: reply msg: {msg:?}").as_str());
+
+    let Some(transfer) = REPLY_PAYLOADS.may_load(deps.storage, msg.id)? else {
+        return Err(StdError::generic_err(format!(
+            "unsupported reply message id {}",
+            msg.id
+        )));
+    };
+    REPLY_PAYLOADS.remove(deps.storage, msg.id);
+
+    let resp: MsgIbcTransferResponse = serde_json_wasm::from_slice(
+        msg.result
+            .into_result()
+            .map_err(StdError::generic_err)?
+            .data
+            .ok_or_else(|| StdError::generic_err("no result"))?
+            .as_slice(),
+    )
+    .map_err(|e| StdError::generic_err(format!("failed to parse response: {e:?}")))?;
+    PENDING_IBC_TRANSFERS.save(deps.storage, (resp.channel, resp.sequence_id), &transfer)?;
+
+    Ok(Response::default().add_attribute("method", "reply_ibc_transfer"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(
+    deps: ExecuteDeps,
+    env: Env,
+    msg: ContractSudoMsg,
+) -> Result<Response<NeutronMsg>, ContractError> {
+    deps.api
+        .debug(format!("WASMDEBUG: sudo: received sudo msg: {msg:?}").as_str());
+
+    match msg {
+        ContractSudoMsg::Cron(CronSudoMsg::CronTick {}) => sudo_cron_tick(deps, env),
+        ContractSudoMsg::Neutron(msg) => Ok(sudo_neutron(deps, env, msg)?),
+    }
+}
+
+/// distributes on behalf of the configured cron schedule. the splits
+/// are paid out in full as there is no keeper to take a tip.
+fn sudo_cron_tick(deps: ExecuteDeps, env: Env) -> Result<Response<NeutronMsg>, ContractError> {
+    let Some(schedule) = CRON_SCHEDULE.may_load(deps.storage)? else {
+        return Err(ContractError::CronNotConfigured {});
+    };
+
+    Ok(try_distribute(deps, env, None)?.add_attribute("cron_schedule", schedule))
+}
+
+fn sudo_neutron(deps: ExecuteDeps, env: Env, msg: SudoMsg) -> StdResult<Response<NeutronMsg>> {
     match msg {
-        QueryMsg::DenomSplit { denom } => Ok(to_binary(&query_split(deps, denom)?)?),
+        // transfer arrived, nothing else to do
+        SudoMsg::Response { request, .. } => {
+            take_pending_ibc_transfer(deps, request)?;
+            Ok(Response::default().add_attribute("method", "sudo_response"))
+        }
+        SudoMsg::Error { request, details } => sudo_refund(deps, env, request, details),
+        SudoMsg::Timeout { request } => sudo_refund(deps, env, request, "timeout".to_string()),
+        _ => Ok(Response::default()),
+    }
+}
+
+/// failed and timed out transfers are refunded to this contract, so we
+/// credit them to the receiver to be claimed on neutron. failed swaps
+/// are kept track of as well.
+fn sudo_refund(
+    mut deps: ExecuteDeps,
+    env: Env,
+    request: RequestPacket,
+    error: String,
+) -> StdResult<Response<NeutronMsg>> {
+    let Some((key, transfer)) = take_pending_ibc_transfer(deps.branch(), request)? else {
+        return Ok(Response::default().add_attribute("method", "sudo_refund"));
+    };
+
+    if let Some(payout_denom) = transfer.payout_denom {
+        FAILED_SWAPS.save(
+            deps.storage,
+            key,
+            &FailedSwap {
+                addr: transfer.addr.to_string(),
+                coin: transfer.coin.clone(),
+                payout_denom,
+                error,
+                failed_at: env.block.time,
+            },
+        )?;
+    }
+    credit_claimable(
+        deps.storage,
+        transfer.addr,
+        transfer.coin.denom,
+        transfer.coin.amount,
+    )?;
+
+    Ok(Response::default().add_attribute("method", "sudo_refund"))
+}
+
+/// removes and returns the pending transfer the packet belongs to,
+/// along with its (channel id, sequence) key
+fn take_pending_ibc_transfer(
+    deps: ExecuteDeps,
+    request: RequestPacket,
+) -> StdResult<Option<((String, u64), IbcTransferInfo)>> {
+    let sequence = request
+        .sequence
+        .ok_or_else(|| StdError::generic_err("sequence not found"))?;
+    let channel_id = request
+        .source_channel
+        .ok_or_else(|| StdError::generic_err("channel_id not found"))?;
+
+    let key = (channel_id, sequence);
+    let transfer = PENDING_IBC_TRANSFERS.may_load(deps.storage, key.clone())?;
+    PENDING_IBC_TRANSFERS.remove(deps.storage, key.clone());
+
+    Ok(transfer.map(|transfer| (key, transfer)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: ExecuteDeps, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    deps.api.debug("WASMDEBUG: migrate");
+
+    // we only migrate splitters, and never to an older version
+    let (stored, stored_version) = assert_migration(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if stored_version < semver::Version::new(1, 1, 0) {
+        // v1.0.0 stored plain split configs with integer percentage shares.
+        // we convert them into custom splits with decimal shares.
+        let legacy_splits = LEGACY_SPLIT_CONFIG_MAP
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(String, LegacySplitConfig)>>>()?;
+        for (denom, legacy_split) in legacy_splits {
+            let split = migrate_legacy_split(legacy_split)?;
+            SPLIT_CONFIG_MAP.save(deps.storage, denom, &split)?;
+        }
+
+        if let Some(legacy_split) = LEGACY_FALLBACK_SPLIT.may_load(deps.storage)? {
+            FALLBACK_SPLIT.save(deps.storage, &migrate_legacy_split(legacy_split)?)?;
+        }
+
+        // IBC receivers did not exist in v1.0.0, their fee has to be set
+        IBC_FEE.save(deps.storage, &query_min_ibc_fee(deps.as_ref())?)?;
+    }
+
+    if let Some(owner) = msg.owner {
+        if OWNER.may_load(deps.storage)?.is_none() {
+            OWNER.save(deps.storage, &deps.api.addr_validate(&owner)?)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "protocol_guild_splitter_migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+fn migrate_legacy_split(legacy_split: LegacySplitConfig) -> Result<SplitType, ContractError> {
+    // zero shares used to be allowed but are rejected by now, so
+    // we drop those receivers
+    let receivers = legacy_split
+        .receivers
+        .into_iter()
+        .filter(|r| !r.share.is_zero())
+        .map(|r| {
+            // shares were percentages, so anything that does not
+            // fit a u64 can not have summed up to 100 either
+            let percent =
+                u64::try_from(r.share.u128()).map_err(|_| ContractError::SplitMisconfig {})?;
+            Ok(Receiver {
+                addr: r.addr,
+                share: Decimal::percent(percent),
+                ibc: None,
+                payout_denom: None,
+            })
+        })
+        .collect::<Result<Vec<Receiver>, ContractError>>()?;
+
+    SplitType::Custom(SplitConfig { receivers }).validate()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: QueryDeps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::DenomSplit { denom } => {
+            let denom = get_ibc_denom(&denom).unwrap_or(denom);
+            Ok(to_binary(&query_split(deps, denom)?)?)
+        }
         QueryMsg::Splits {} => Ok(to_binary(&query_all_splits(deps)?)?),
         QueryMsg::FallbackSplit {} => Ok(to_binary(&FALLBACK_SPLIT.may_load(deps.storage)?)?),
-        QueryMsg::DepositAddress {} => Ok(to_binary(&Some(env.contract.address))?),
+        QueryMsg::MinDistribution { denom } => {
+            let denom = get_ibc_denom(&denom).unwrap_or(denom);
+            Ok(to_binary(&query_min_distribution(deps, denom)?)?)
+        }
+        QueryMsg::DenomAliases {} => Ok(to_binary(&query_denom_aliases(deps)?)?),
+        QueryMsg::DepositAddress {} => {
+            let deposit_address: DepositAddressResponse = Some(env.contract.address.to_string());
+            Ok(to_binary(&deposit_address)?)
+        }
+        QueryMsg::Ownership {} => Ok(to_binary(&query_ownership(deps)?)?),
+        QueryMsg::Members {} => Ok(to_binary(&query_members(deps)?)?),
+        QueryMsg::TimeWeightedSplit {} => Ok(to_binary(&get_time_weighted_config(deps, &env)?)?),
+        QueryMsg::DistributionMode {} => Ok(to_binary(&load_distribution_mode(deps.storage)?)?),
+        QueryMsg::Claimable { addr } => Ok(to_binary(&query_claimable(deps, addr)?)?),
+        QueryMsg::RemainderPolicy {} => Ok(to_binary(&load_remainder_policy(deps.storage)?)?),
+        QueryMsg::CarriedDust {} => Ok(to_binary(&query_carried_dust(deps)?)?),
+        QueryMsg::Cw20Splits {} => Ok(to_binary(&query_cw20_splits(deps)?)?),
+        QueryMsg::IbcFee {} => Ok(to_binary(&IBC_FEE.load(deps.storage)?)?),
+        QueryMsg::PendingIbcTransfers {} => Ok(to_binary(&query_pending_ibc_transfers(deps)?)?),
+        QueryMsg::DistributionHistory { start_after, limit } => Ok(to_binary(
+            &query_distribution_history(deps, start_after, limit)?,
+        )?),
+        QueryMsg::ReceiverTotals { addr } => Ok(to_binary(&query_receiver_totals(deps, addr)?)?),
+        QueryMsg::DenomTotals {} => Ok(to_binary(&query_denom_totals(deps)?)?),
+        QueryMsg::Paused {} => Ok(to_binary(&is_paused(deps.storage)?)?),
+        QueryMsg::Guardian {} => Ok(to_binary(&GUARDIAN.may_load(deps.storage)?)?),
+        QueryMsg::KeeperTip {} => Ok(to_binary(&KeeperTipResponse {
+            keeper_tip: KEEPER_TIP.may_load(deps.storage)?,
+            last_paid_tick: LAST_PAID_TICK.may_load(deps.storage)?,
+        })?),
+        QueryMsg::CronSchedule {} => Ok(to_binary(&CRON_SCHEDULE.may_load(deps.storage)?)?),
+        QueryMsg::SwapConfig {} => Ok(to_binary(&SWAP_CONFIG.may_load(deps.storage)?)?),
+        QueryMsg::FailedSwaps { start_after, limit } => {
+            Ok(to_binary(&query_failed_swaps(deps, start_after, limit)?)?)
+        }
     }
 }
 
-pub fn query_all_splits(deps: Deps) -> Result<Vec<(String, SplitConfig)>, StdError> {
-    let mut splits: Vec<(String, SplitConfig)> = vec![];
+/// contracts instantiated before distribution modes existed push their splits
+fn load_distribution_mode(storage: &dyn Storage) -> StdResult<DistributionMode> {
+    Ok(DISTRIBUTION_MODE
+        .may_load(storage)?
+        .unwrap_or(DistributionMode::Push))
+}
+
+/// contracts instantiated before remainder policies existed carry their
+/// remainders forward
+fn load_remainder_policy(storage: &dyn Storage) -> StdResult<RemainderPolicy> {
+    Ok(REMAINDER_POLICY
+        .may_load(storage)?
+        .unwrap_or(RemainderPolicy::CarryForward))
+}
+
+pub fn query_all_splits(deps: QueryDeps) -> Result<Vec<(String, SplitType)>, StdError> {
+    let mut splits: Vec<(String, SplitType)> = vec![];
 
     for entry in SPLIT_CONFIG_MAP.range(deps.storage, None, None, Order::Ascending) {
-        let (denom, config) = entry?;
-        splits.push((denom, config));
+        let (denom, split) = entry?;
+        splits.push((denom, split));
     }
 
     Ok(splits)
 }
 
-pub fn query_split(deps: Deps, denom: String) -> Result<SplitConfig, StdError> {
+pub fn query_split(deps: QueryDeps, denom: String) -> Result<SplitType, StdError> {
     for entry in SPLIT_CONFIG_MAP.range(deps.storage, None, None, Order::Ascending) {
-        let (entry_denom, config) = entry?;
+        let (entry_denom, split) = entry?;
         if entry_denom == denom {
-            return Ok(config);
+            return Ok(split);
         }
     }
 
-    Ok(SplitConfig { receivers: vec![] })
+    Ok(SplitType::Custom(SplitConfig { receivers: vec![] }))
+}
+
+pub fn query_denom_aliases(deps: QueryDeps) -> StdResult<Vec<(String, String)>> {
+    DENOM_ALIASES
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+/// minimum distribution of the split the denom falls under
+pub fn query_min_distribution(deps: QueryDeps, denom: String) -> StdResult<Uint128> {
+    let min_distribution = if SPLIT_CONFIG_MAP.has(deps.storage, denom.to_string()) {
+        MIN_DISTRIBUTIONS.may_load(deps.storage, denom)?
+    } else {
+        FALLBACK_MIN_DISTRIBUTION.may_load(deps.storage)?
+    };
+
+    Ok(min_distribution.unwrap_or_default())
+}
+
+pub fn query_members(deps: QueryDeps) -> Result<Vec<Member>, StdError> {
+    let mut members: Vec<Member> = vec![];
+
+    for entry in MEMBERS.range(deps.storage, None, None, Order::Ascending) {
+        let (_, member) = entry?;
+        members.push(member);
+    }
+
+    Ok(members)
+}
+
+pub fn query_claimable(deps: QueryDeps, addr: String) -> Result<Vec<Coin>, StdError> {
+    let mut claimable: Vec<Coin> = vec![];
+
+    for entry in CLAIMABLE
+        .prefix(addr)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (denom, amount) = entry?;
+        claimable.push(Coin { denom, amount });
+    }
+
+    Ok(claimable)
+}
+
+pub fn query_carried_dust(deps: QueryDeps) -> Result<Vec<Coin>, StdError> {
+    let mut dust: Vec<Coin> = vec![];
+
+    for entry in CARRIED_DUST.range(deps.storage, None, None, Order::Ascending) {
+        let (denom, amount) = entry?;
+        dust.push(Coin { denom, amount });
+    }
+
+    Ok(dust)
+}
+
+pub fn query_cw20_splits(deps: QueryDeps) -> Result<Vec<(Addr, SplitType)>, StdError> {
+    let mut splits: Vec<(Addr, SplitType)> = vec![];
+
+    for entry in CW20_SPLIT_CONFIG_MAP.range(deps.storage, None, None, Order::Ascending) {
+        let (token, split) = entry?;
+        splits.push((token, split));
+    }
+
+    Ok(splits)
+}
+
+#[allow(clippy::type_complexity)]
+pub fn query_pending_ibc_transfers(
+    deps: QueryDeps,
+) -> Result<Vec<((String, u64), IbcTransferInfo)>, StdError> {
+    PENDING_IBC_TRANSFERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn query_distribution_history(
+    deps: QueryDeps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Vec<DistributionRecord>, StdError> {
+    let limit = limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    DISTRIBUTION_HISTORY
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| entry.map(|(_, record)| record))
+        .collect()
+}
+
+pub fn query_failed_swaps(
+    deps: QueryDeps,
+    start_after: Option<(String, u64)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<((String, u64), FailedSwap)>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(MAX_HISTORY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    FAILED_SWAPS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn query_receiver_totals(deps: QueryDeps, addr: String) -> Result<Vec<Coin>, StdError> {
+    let mut totals: Vec<Coin> = vec![];
+
+    for entry in RECEIVER_TOTALS
+        .prefix(addr)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (denom, amount) = entry?;
+        totals.push(Coin { denom, amount });
+    }
+
+    Ok(totals)
+}
+
+pub fn query_denom_totals(deps: QueryDeps) -> Result<Vec<Coin>, StdError> {
+    let mut totals: Vec<Coin> = vec![];
+
+    for entry in DENOM_TOTALS.range(deps.storage, None, None, Order::Ascending) {
+        let (denom, amount) = entry?;
+        totals.push(Coin { denom, amount });
+    }
+
+    Ok(totals)
 }
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use guild_interfaces::{admin::AdminError, fee::IbcFeeError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,9 +7,33 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    IbcFee(#[from] IbcFeeError),
+
     #[error("misconfigured split")]
     SplitMisconfig {},
 
-    #[error("unauthorized caller")]
-    Unauthorized {},
+    #[error("misconfigured guild member")]
+    MemberMisconfig {},
+
+    #[error("nothing to claim")]
+    NothingToClaim {},
+
+    #[error("cw20 token is not allowlisted")]
+    Cw20NotAllowed {},
+
+    #[error("keeper tip must be between 1 and 1000 bps with a non-zero min interval")]
+    InvalidKeeperTip {},
+
+    #[error("no cron schedule configured")]
+    CronNotConfigured {},
+
+    #[error("misconfigured swap venue")]
+    InvalidSwapConfig {},
+
+    #[error("nothing to withdraw")]
+    NothingToWithdraw {},
 }
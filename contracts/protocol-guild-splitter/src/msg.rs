@@ -1,5 +1,14 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{BankMsg, Coin, CosmosMsg, Uint128};
+use std::collections::HashSet;
+
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, StdError, StdResult, Timestamp, Uint128,
+    Uint64, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use guild_interfaces::{admin::OwnershipResponse, deposit::DepositAddressResponse};
+use neutron_sdk::bindings::msg::IbcFee;
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
 
@@ -10,31 +19,370 @@ pub struct InstantiateMsg {
     /// a split for all denoms that are not covered in the
     /// regular `splits` list
     pub fallback_split: Option<SplitType>,
+    /// balance a denom split by the fallback needs to reach before
+    /// it gets distributed
+    pub fallback_min_distribution: Option<Uint128>,
+    /// address allowed to update the splits. if not provided,
+    /// the split configuration is immutable
+    pub owner: Option<String>,
+    /// guild members used by `TimeWeighted` splits
+    #[serde(default)]
+    pub members: Vec<Member>,
+    /// how the splits are paid out. defaults to `Push`
+    pub distribution_mode: Option<DistributionMode>,
+    /// what happens to the rounding remainder of every split.
+    /// defaults to `CarryForward`
+    pub remainder_policy: Option<RemainderPolicy>,
+    /// allowlist of cw20 tokens along with their splits
+    #[serde(default)]
+    pub cw20_splits: Vec<Cw20Split>,
+    /// fee paid for every IBC transfer to receivers on other chains.
+    /// defaults to the minimum fee required by the feerefunder module
+    pub ibc_fee: Option<IbcFee>,
+    /// address allowed to pause and unpause besides the owner
+    pub guardian: Option<String>,
+    /// tip paid to the sender of a tick. no tips are paid if not provided
+    pub keeper_tip: Option<KeeperTip>,
+    /// name of the neutron cron schedule ticking the splitter.
+    /// cron ticks are rejected if not provided
+    pub cron_schedule: Option<String>,
+    /// osmosis swap venue for receivers with a payout denom. receivers
+    /// are paid in the split denom if not provided
+    pub swap_config: Option<SwapConfig>,
+}
+
+/// osmosis swap venue used to pay receivers in their payout denom.
+/// splits are sent over IBC to the crosschain swaps contract on osmosis
+/// through ibc-hooks, which swaps them and forwards the output to the
+/// receiver.
+#[cw_serde]
+pub struct SwapConfig {
+    /// transfer channel from neutron to osmosis
+    pub channel_id: String,
+    /// address of the crosschain swaps contract on osmosis
+    pub swap_contract: String,
+    /// maximum slippage against the osmosis TWAP price in percent.
+    /// must be above zero and at most 50
+    pub max_slippage_percentage: Decimal,
+    /// window of the TWAP price in seconds
+    pub twap_window: Uint64,
+    /// osmosis address receiving swap outputs that fail to be delivered
+    pub recovery_addr: String,
+    /// timeout of the transfer to osmosis in seconds
+    pub timeout: Uint64,
+}
+
+/// highest slippage against the TWAP price a swap venue may allow, in percent
+const MAX_SLIPPAGE_PERCENTAGE: u64 = 50;
+
+impl SwapConfig {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.channel_id.is_empty()
+            || self.swap_contract.is_empty()
+            || self.recovery_addr.is_empty()
+            || self.max_slippage_percentage.is_zero()
+            || self.max_slippage_percentage > Decimal::percent(MAX_SLIPPAGE_PERCENTAGE * 100)
+            || self.twap_window.is_zero()
+            || self.timeout.is_zero()
+        {
+            return Err(ContractError::InvalidSwapConfig {});
+        }
+        Ok(())
+    }
+
+    /// ibc-hooks memo swapping the transferred funds into `output_denom`
+    /// and sending them to `receiver`
+    pub fn get_swap_memo(&self, output_denom: &str, receiver: &str) -> StdResult<String> {
+        let memo = SwapMemo {
+            wasm: SwapMemoWasm {
+                contract: self.swap_contract.to_string(),
+                msg: CrosschainSwapsMsg::OsmosisSwap {
+                    output_denom: output_denom.to_string(),
+                    slippage: SwapSlippage::Twap {
+                        slippage_percentage: self.max_slippage_percentage,
+                        window_seconds: Some(self.twap_window.u64()),
+                    },
+                    receiver: receiver.to_string(),
+                    on_failed_delivery: FailedDeliveryAction::LocalRecoveryAddr(
+                        self.recovery_addr.to_string(),
+                    ),
+                },
+            },
+        };
+        serde_json_wasm::to_string(&memo).map_err(|e| StdError::generic_err(e.to_string()))
+    }
+}
+
+#[cw_serde]
+struct SwapMemo {
+    wasm: SwapMemoWasm,
+}
+
+#[cw_serde]
+struct SwapMemoWasm {
+    contract: String,
+    msg: CrosschainSwapsMsg,
+}
+
+/// execute message of the osmosis crosschain swaps contract
+#[cw_serde]
+enum CrosschainSwapsMsg {
+    OsmosisSwap {
+        output_denom: String,
+        slippage: SwapSlippage,
+        receiver: String,
+        on_failed_delivery: FailedDeliveryAction,
+    },
+}
+
+#[cw_serde]
+enum SwapSlippage {
+    Twap {
+        slippage_percentage: Decimal,
+        window_seconds: Option<u64>,
+    },
+}
+
+#[cw_serde]
+enum FailedDeliveryAction {
+    LocalRecoveryAddr(String),
+}
+
+/// tip paid to whoever ticks the splitter, as a share of every
+/// distributed denom. at most one tick per `min_interval` seconds is
+/// paid, so that spamming ticks does not pay off.
+#[cw_serde]
+pub struct KeeperTip {
+    /// share of each distributed amount in basis points, at most 1000
+    pub bps: u16,
+    pub min_interval: Uint64,
+}
+
+/// highest keeper tip in basis points
+const MAX_KEEPER_TIP_BPS: u16 = 1_000;
+
+impl KeeperTip {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.bps == 0 || self.bps > MAX_KEEPER_TIP_BPS || self.min_interval.is_zero() {
+            return Err(ContractError::InvalidKeeperTip {});
+        }
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub struct Cw20Split {
+    /// address of the cw20 contract
+    pub token: String,
+    /// type of split configuration to be applied
+    pub split: SplitType,
+}
+
+/// cw20 balances are tracked next to native balances under
+/// internal denoms of the form `cw20:<token address>`
+pub const CW20_DENOM_PREFIX: &str = "cw20:";
+
+pub fn cw20_denom(token: &Addr) -> String {
+    format!("{CW20_DENOM_PREFIX}{token}")
+}
+
+/// builds a transfer of either a native denom or a cw20 denom
+pub fn get_transfer_msg<T>(
+    to_address: String,
+    denom: &str,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<T>> {
+    match denom.strip_prefix(CW20_DENOM_PREFIX) {
+        Some(token) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: to_address,
+                amount,
+            })?,
+            funds: vec![],
+        })),
+        None => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address,
+            amount: vec![Coin {
+                denom: denom.to_string(),
+                amount,
+            }],
+        })),
+    }
+}
+
+#[cw_serde]
+pub enum DistributionMode {
+    /// every tick sends the split amounts straight to the receivers
+    Push,
+    /// every tick credits the split amounts to the receivers, who
+    /// then withdraw them with `Claim`
+    Accrue,
+}
+
+#[cw_serde]
+pub enum RemainderPolicy {
+    /// remainder is added to the receiver with the largest share
+    LargestShare,
+    /// remainder is sent to the given address on neutron
+    Receiver(String),
+    /// remainder stays in the contract and is split again on the next tick
+    CarryForward,
+}
+
+/// `ibc/<hash>` denom of a denom trace such as `transfer/channel-0/uatom`,
+/// or `None` if the denom is not a trace
+pub fn get_ibc_denom(denom: &str) -> Option<String> {
+    let mut parts = denom.splitn(3, '/');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(port), Some(channel), Some(base))
+            if !port.is_empty() && channel.starts_with("channel-") && !base.is_empty() =>
+        {
+            Some(format!("ibc/{:X}", Sha256::digest(denom.as_bytes())))
+        }
+        _ => None,
+    }
 }
 
 #[cw_serde]
 pub struct DenomSplit {
-    /// denom which we wish to split up
+    /// denom which we wish to split up. IBC denoms can also be given
+    /// by their trace, e.g. `transfer/channel-0/uatom`
     pub denom: String,
     /// type of split configuration to be applied
     pub split: SplitType,
+    /// balance the denom needs to reach before it gets distributed.
+    /// smaller balances are kept until a later tick.
+    pub min_distribution: Option<Uint128>,
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
     Tick {},
+    /// adds or replaces the splits of the given denoms and removes
+    /// the splits of denoms listed in `remove`. owner only.
+    UpdateSplits {
+        set: Vec<DenomSplit>,
+        remove: Vec<String>,
+    },
+    /// sets the fallback split along with its minimum distribution,
+    /// or clears it if `None`. owner only.
+    UpdateFallback {
+        split: Option<SplitType>,
+        min_distribution: Option<Uint128>,
+    },
+    /// proposes a new owner that has to accept the ownership. owner only.
+    TransferOwnership {
+        new_owner: String,
+    },
+    /// accepts the pending ownership transfer. pending owner only.
+    AcceptOwnership {},
+    /// adds or replaces the given guild members and removes the
+    /// members listed in `remove`. owner only.
+    UpdateMembers {
+        set: Vec<Member>,
+        remove: Vec<String>,
+    },
+    /// switches between pushing and accruing distributions. owner only.
+    UpdateDistributionMode {
+        mode: DistributionMode,
+    },
+    /// sets what happens to the rounding remainder of every split. owner only.
+    UpdateRemainderPolicy {
+        policy: RemainderPolicy,
+    },
+    /// withdraws the sender's claimable balances of the given denoms,
+    /// or of all denoms if `None`
+    Claim {
+        denoms: Option<Vec<String>>,
+    },
+    /// receives allowlisted cw20 tokens to be split on the next tick
+    Receive(Cw20ReceiveMsg),
+    /// adds or replaces the splits of the given cw20 tokens and removes
+    /// the tokens listed in `remove` from the allowlist. owner only.
+    UpdateCw20Splits {
+        set: Vec<Cw20Split>,
+        remove: Vec<String>,
+    },
+    /// stops ticks from distributing. owner or guardian only.
+    Pause {},
+    /// lets ticks distribute again. owner or guardian only.
+    Unpause {},
+    /// sets or removes the guardian. owner only.
+    UpdateGuardian {
+        guardian: Option<String>,
+    },
+    /// sends all native and allowlisted cw20 balances that are not owed
+    /// to receivers to the recovery address. claimable balances stay
+    /// claimable. IBC transfers awaiting their acknowledgement are no
+    /// longer tracked. owner only.
+    EmergencyWithdraw {
+        recovery_address: String,
+    },
+    /// sets or removes the tip paid to the sender of a tick. owner only.
+    UpdateKeeperTip {
+        keeper_tip: Option<KeeperTip>,
+    },
+    /// sets or removes the name of the cron schedule ticking the
+    /// splitter. owner only.
+    UpdateCronSchedule {
+        schedule: Option<String>,
+    },
+    /// sets or removes the osmosis swap venue. owner only.
+    UpdateSwapConfig {
+        swap_config: Option<SwapConfig>,
+    },
+    /// sets the fees escrowed for every ibc transfer. owner only.
+    UpdateIbcFee {
+        ibc_fee: IbcFee,
+    },
 }
 
 #[cw_serde]
 pub enum SplitType {
     Custom(SplitConfig),
+    /// split between the active guild members, weighted by the time
+    /// they have been active multiplied by their weight factor
+    TimeWeighted {},
 }
 
 impl SplitType {
-    pub fn get_split_config(self) -> Result<SplitConfig, ContractError> {
+    pub fn validate(self) -> Result<SplitType, ContractError> {
         match self {
-            SplitType::Custom(c) => Ok(c),
+            SplitType::Custom(c) => Ok(SplitType::Custom(c.validate()?)),
+            SplitType::TimeWeighted {} => Ok(SplitType::TimeWeighted {}),
+        }
+    }
+}
+
+/// upper bound of member weights, keeping the weighted shares far
+/// from overflowing
+pub const MAX_MEMBER_WEIGHT: u128 = 10_000;
+
+#[cw_serde]
+pub struct Member {
+    /// address on neutron
+    pub addr: String,
+    /// time since which the member is contributing to the guild
+    pub start: Timestamp,
+    /// part-time/full-time factor, e.g. 100 for full-time
+    /// and 50 for half-time members. at most `MAX_MEMBER_WEIGHT`.
+    pub weight: Uint128,
+    /// inactive members are left out of time weighted splits
+    pub active: bool,
+}
+
+impl Member {
+    /// share of the member at the given time, given the seconds it spent
+    /// inactive since its start. members that are inactive or have not
+    /// started yet are not entitled to anything.
+    pub fn share_at(&self, time: Timestamp, inactive_seconds: u64) -> StdResult<Uint128> {
+        if !self.active || time <= self.start {
+            return Ok(Uint128::zero());
         }
+
+        let time_active = (time.seconds() - self.start.seconds()).saturating_sub(inactive_seconds);
+        Ok(Uint128::from(time_active).checked_mul(self.weight)?)
     }
 }
 
@@ -47,56 +395,379 @@ pub struct SplitConfig {
 pub struct Receiver {
     /// address on neutron
     pub addr: String,
-    /// share of the receiver denominated in percentages
-    pub share: Uint128,
+    /// share of the receiver as a fraction of the total, e.g. 0.3333
+    pub share: Decimal,
+    /// if set, native denoms are sent over IBC to a remote address
+    /// instead. `addr` can claim the funds of failed transfers.
+    pub ibc: Option<IbcDestination>,
+    /// denom on osmosis the receiver wants to be paid in. native splits
+    /// of other denoms are swapped on osmosis and the output is sent to
+    /// the receiver. only applies to pushed splits.
+    pub payout_denom: Option<String>,
+}
+
+#[cw_serde]
+pub struct IbcDestination {
+    /// transfer channel from neutron to the remote chain
+    pub channel_id: String,
+    /// address of the receiver on the remote chain
+    pub remote_addr: String,
+    /// timeout of the transfer in seconds
+    pub timeout: Uint64,
+}
+
+/// amount a receiver is entitled to out of a split
+#[cw_serde]
+pub struct Entitlement {
+    pub addr: String,
+    pub ibc: Option<IbcDestination>,
+    pub payout_denom: Option<String>,
+    pub amount: Uint128,
+}
+
+/// IBC transfer to a receiver on another chain that is awaiting
+/// its acknowledgement
+#[cw_serde]
+pub struct IbcTransferInfo {
+    /// neutron address of the receiver, credited on failure
+    pub addr: String,
+    pub remote_addr: String,
+    pub coin: Coin,
+    /// denom the transfer gets swapped into on osmosis, if any
+    #[serde(default)]
+    pub payout_denom: Option<String>,
 }
 
 impl SplitConfig {
+    /// a valid split has no zero shares, no duplicate receivers
+    /// and shares that sum up to exactly 1
     pub fn validate(self) -> Result<SplitConfig, ContractError> {
-        let total_share: Uint128 = self.receivers.iter().map(|r| r.share).sum();
+        let mut receiver_addrs: HashSet<&str> = HashSet::new();
+        for receiver in self.receivers.iter() {
+            if receiver.share.is_zero() || !receiver_addrs.insert(receiver.addr.as_str()) {
+                return Err(ContractError::SplitMisconfig {});
+            }
+            if let Some(ibc) = &receiver.ibc {
+                if ibc.channel_id.is_empty() || ibc.remote_addr.is_empty() || ibc.timeout.is_zero()
+                {
+                    return Err(ContractError::SplitMisconfig {});
+                }
+            }
+            if receiver.payout_denom.as_deref() == Some("") {
+                return Err(ContractError::SplitMisconfig {});
+            }
+        }
+
+        let total_share: Decimal = self.receivers.iter().map(|r| r.share).sum();
 
-        if total_share == Uint128::new(100) {
+        if total_share == Decimal::one() {
             Ok(self)
         } else {
             Err(ContractError::SplitMisconfig {})
         }
     }
 
-    pub fn get_transfer_messages(
+    /// splits the amount between the receivers proportionally to their
+    /// share of the total. for validated custom splits the total is 1.
+    /// every entitlement is rounded down and the remainder is handled
+    /// according to the policy. returns the entitlements along with the
+    /// remainder that is carried forward.
+    pub fn get_entitlements(
         &self,
         amount: Uint128,
-        denom: String,
-    ) -> Result<Vec<CosmosMsg>, ContractError> {
-        let mut msgs: Vec<CosmosMsg> = vec![];
+        remainder_policy: &RemainderPolicy,
+    ) -> Result<(Vec<Entitlement>, Uint128), ContractError> {
+        let mut entitlements: Vec<Entitlement> = vec![];
+        let total_share: Decimal = self.receivers.iter().map(|r| r.share).sum();
+
+        // nobody to split between, so there is no remainder either
+        if total_share.is_zero() {
+            return Ok((entitlements, Uint128::zero()));
+        }
 
         for receiver in self.receivers.iter() {
             let entitlement = amount
-                .checked_multiply_ratio(receiver.share, Uint128::new(100))
+                .checked_multiply_ratio(receiver.share.atomics(), total_share.atomics())
                 .map_err(|_| ContractError::SplitMisconfig {})?;
 
-            let amount = Coin {
-                denom: denom.to_string(),
+            entitlements.push(Entitlement {
+                addr: receiver.addr.to_string(),
+                ibc: receiver.ibc.clone(),
+                payout_denom: receiver.payout_denom.clone(),
                 amount: entitlement,
-            };
+            });
+        }
+
+        let distributed: Uint128 = entitlements.iter().map(|e| e.amount).sum();
+        let remainder = amount.checked_sub(distributed).map_err(StdError::from)?;
+        if remainder.is_zero() {
+            return Ok((entitlements, remainder));
+        }
 
-            msgs.push(CosmosMsg::Bank(BankMsg::Send {
-                to_address: receiver.addr.to_string(),
-                amount: vec![amount],
-            }));
+        match remainder_policy {
+            RemainderPolicy::LargestShare => {
+                // receivers and entitlements share the same order, so the
+                // largest share also has the largest entitlement
+                let largest = self
+                    .receivers
+                    .iter()
+                    .enumerate()
+                    .max_by(|(i, a), (j, b)| a.share.cmp(&b.share).then(j.cmp(i)))
+                    .map(|(i, _)| i);
+                if let Some(index) = largest {
+                    entitlements[index].amount += remainder;
+                }
+                Ok((entitlements, Uint128::zero()))
+            }
+            RemainderPolicy::Receiver(addr) => {
+                entitlements.push(Entitlement {
+                    addr: addr.to_string(),
+                    ibc: None,
+                    payout_denom: None,
+                    amount: remainder,
+                });
+                Ok((entitlements, Uint128::zero()))
+            }
+            RemainderPolicy::CarryForward => Ok((entitlements, remainder)),
         }
-        Ok(msgs)
     }
 }
 
+/// everything that was split during a single tick
+#[cw_serde]
+pub struct DistributionRecord {
+    pub id: u64,
+    pub height: u64,
+    pub time: Timestamp,
+    pub distributions: Vec<DenomDistribution>,
+}
+
+#[cw_serde]
+pub struct DenomDistribution {
+    pub denom: String,
+    pub total: Uint128,
+    /// (receiver address, amount) pairs
+    pub receivers: Vec<(String, Uint128)>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// owner to set if the contract has none, e.g. when migrating
+    /// from 1.0.0 which had no owner
+    pub owner: Option<String>,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    #[returns(SplitConfig)]
+    /// split of the denom, or an empty custom split if it has none.
+    /// returned a plain `SplitConfig` before time weighted splits were
+    /// added, so clients have to read the `custom` variant.
+    #[returns(SplitType)]
     DenomSplit { denom: String },
-    #[returns(Vec<(String, SplitConfig)>)]
+    #[returns(Vec<(String, SplitType)>)]
     Splits {},
-    #[returns(SplitConfig)]
+    #[returns(Option<SplitType>)]
     FallbackSplit {},
-    #[returns(String)]
+    /// denom traces the splits were configured with, mapped to their
+    /// `ibc/<hash>` denoms
+    #[returns(Vec<(String, String)>)]
+    DenomAliases {},
+    /// balance the denom needs to reach before it gets distributed
+    #[returns(Uint128)]
+    MinDistribution { denom: String },
+    /// the splitter itself, see `guild_interfaces::deposit`
+    #[returns(DepositAddressResponse)]
     DepositAddress {},
+    #[returns(OwnershipResponse)]
+    Ownership {},
+    #[returns(Vec<Member>)]
+    Members {},
+    /// receivers and shares a time weighted split would use at the
+    /// current block time
+    #[returns(SplitConfig)]
+    TimeWeightedSplit {},
+    #[returns(DistributionMode)]
+    DistributionMode {},
+    /// balances credited to the address that can be withdrawn with `Claim`
+    #[returns(Vec<Coin>)]
+    Claimable { addr: String },
+    #[returns(RemainderPolicy)]
+    RemainderPolicy {},
+    /// rounding remainders per denom that were left in the contract by
+    /// the last split of that denom
+    #[returns(Vec<Coin>)]
+    CarriedDust {},
+    /// allowlisted cw20 tokens and their splits
+    #[returns(Vec<(Addr, SplitType)>)]
+    Cw20Splits {},
+    #[returns(IbcFee)]
+    IbcFee {},
+    /// IBC transfers to receivers that are awaiting their acknowledgement
+    #[returns(Vec<((String, u64), IbcTransferInfo)>)]
+    PendingIbcTransfers {},
+    /// past distributions in ascending order of their ids
+    #[returns(Vec<DistributionRecord>)]
+    DistributionHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// lifetime amounts split to the receiver per denom
+    #[returns(Vec<Coin>)]
+    ReceiverTotals { addr: String },
+    /// lifetime amounts split per denom
+    #[returns(Vec<Coin>)]
+    DenomTotals {},
+    #[returns(bool)]
+    Paused {},
+    #[returns(Option<Addr>)]
+    Guardian {},
+    #[returns(KeeperTipResponse)]
+    KeeperTip {},
+    /// name of the cron schedule ticking the splitter
+    #[returns(Option<String>)]
+    CronSchedule {},
+    #[returns(Option<SwapConfig>)]
+    SwapConfig {},
+    /// swaps on osmosis that failed and were credited to the receiver
+    /// in the split denom
+    #[returns(Vec<((String, u64), FailedSwap)>)]
+    FailedSwaps {
+        start_after: Option<(String, u64)>,
+        limit: Option<u32>,
+    },
+}
+
+/// swap whose transfer to osmosis got acknowledged with an error or
+/// timed out
+#[cw_serde]
+pub struct FailedSwap {
+    pub addr: String,
+    pub coin: Coin,
+    pub payout_denom: String,
+    pub error: String,
+    pub failed_at: Timestamp,
+}
+
+pub type KeeperTipResponse = guild_interfaces::keeper::KeeperTipResponse<KeeperTip>;
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    fn receiver(addr: &str, share: &str) -> Receiver {
+        Receiver {
+            addr: addr.to_string(),
+            share: share.parse().unwrap(),
+            ibc: None,
+            payout_denom: None,
+        }
+    }
+
+    fn split_config(receivers: &[(&str, &str)]) -> SplitConfig {
+        SplitConfig {
+            receivers: receivers
+                .iter()
+                .map(|(addr, share)| receiver(addr, share))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn split_shares_sum_to_one() {
+        split_config(&[("alice", "0.3333"), ("bob", "0.3333"), ("carol", "0.3334")])
+            .validate()
+            .unwrap();
+
+        for shares in [
+            [("alice", "0.5"), ("bob", "0.4999")],
+            [("alice", "0.5"), ("bob", "0.5001")],
+        ] {
+            let err = split_config(&shares).validate().unwrap_err();
+            assert!(matches!(err, ContractError::SplitMisconfig {}));
+        }
+    }
+
+    #[test]
+    fn split_rejects_zero_shares() {
+        let err = split_config(&[("alice", "1"), ("bob", "0")])
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, ContractError::SplitMisconfig {}));
+    }
+
+    #[test]
+    fn split_rejects_duplicate_receivers() {
+        let err = split_config(&[("alice", "0.5"), ("alice", "0.5")])
+            .validate()
+            .unwrap_err();
+        assert!(matches!(err, ContractError::SplitMisconfig {}));
+    }
+
+    fn entitlement_amounts(entitlements: &[Entitlement]) -> Vec<(&str, u128)> {
+        entitlements
+            .iter()
+            .map(|e| (e.addr.as_str(), e.amount.u128()))
+            .collect()
+    }
+
+    #[test]
+    fn remainder_to_largest_share() {
+        let config = split_config(&[("alice", "0.3"), ("bob", "0.5"), ("carol", "0.2")]);
+        let (entitlements, remainder) = config
+            .get_entitlements(Uint128::new(7), &RemainderPolicy::LargestShare)
+            .unwrap();
+        assert_eq!(
+            entitlement_amounts(&entitlements),
+            vec![("alice", 2), ("bob", 4), ("carol", 1)]
+        );
+        assert!(remainder.is_zero());
+
+        // ties go to the receiver listed first
+        let config = split_config(&[("alice", "0.5"), ("bob", "0.5")]);
+        let (entitlements, _) = config
+            .get_entitlements(Uint128::new(3), &RemainderPolicy::LargestShare)
+            .unwrap();
+        assert_eq!(
+            entitlement_amounts(&entitlements),
+            vec![("alice", 2), ("bob", 1)]
+        );
+    }
+
+    #[test]
+    fn remainder_to_receiver() {
+        let config = split_config(&[("alice", "0.3"), ("bob", "0.5"), ("carol", "0.2")]);
+        let (entitlements, remainder) = config
+            .get_entitlements(
+                Uint128::new(7),
+                &RemainderPolicy::Receiver("treasury".to_string()),
+            )
+            .unwrap();
+        assert_eq!(
+            entitlement_amounts(&entitlements),
+            vec![("alice", 2), ("bob", 3), ("carol", 1), ("treasury", 1)]
+        );
+        assert!(remainder.is_zero());
+    }
+
+    #[test]
+    fn remainder_carried_forward() {
+        let config = split_config(&[("alice", "0.3"), ("bob", "0.5"), ("carol", "0.2")]);
+        let (entitlements, remainder) = config
+            .get_entitlements(Uint128::new(7), &RemainderPolicy::CarryForward)
+            .unwrap();
+        assert_eq!(
+            entitlement_amounts(&entitlements),
+            vec![("alice", 2), ("bob", 3), ("carol", 1)]
+        );
+        assert_eq!(remainder, Uint128::new(1));
+
+        // nothing to carry if the amount splits evenly
+        let (_, remainder) = config
+            .get_entitlements(Uint128::new(10), &RemainderPolicy::CarryForward)
+            .unwrap();
+        assert!(remainder.is_zero());
+    }
 }
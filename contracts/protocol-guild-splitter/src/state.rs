@@ -1,9 +1,119 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Timestamp, Uint128};
 use cw_storage_plus::{Item, Map};
 
-use crate::msg::SplitConfig;
+use neutron_sdk::bindings::msg::IbcFee;
 
-/// maps a denom string to a vec of SplitReceivers
-pub const SPLIT_CONFIG_MAP: Map<String, SplitConfig> = Map::new("split_config");
+use crate::msg::{
+    DistributionMode, DistributionRecord, FailedSwap, IbcTransferInfo, KeeperTip, Member,
+    RemainderPolicy, SplitType, SwapConfig,
+};
+
+/// maps a denom string to its validated split
+pub const SPLIT_CONFIG_MAP: Map<String, SplitType> = Map::new("split_config");
+
+/// allowlist of cw20 token contracts mapped to their validated split
+pub const CW20_SPLIT_CONFIG_MAP: Map<Addr, SplitType> = Map::new("cw20_split_config");
 
 /// split for all denoms that are not explicitly defined in SPLIT_CONFIG_MAP
-pub const FALLBACK_SPLIT: Item<SplitConfig> = Item::new("fallback_split");
+pub const FALLBACK_SPLIT: Item<SplitType> = Item::new("fallback_split");
+
+/// denom trace -> `ibc/<hash>` denom it was resolved to. splits given
+/// by their trace are stored under the hashed denom.
+pub const DENOM_ALIASES: Map<String, String> = Map::new("denom_aliases");
+
+/// denom -> balance required before the denom gets distributed
+pub const MIN_DISTRIBUTIONS: Map<String, Uint128> = Map::new("min_distributions");
+
+/// balance required before a denom split by the fallback gets distributed
+pub const FALLBACK_MIN_DISTRIBUTION: Item<Uint128> = Item::new("fallback_min_distribution");
+
+/// guild member registry used by time weighted splits
+pub const MEMBERS: Map<Addr, Member> = Map::new("members");
+
+/// periods guild members spent inactive, which do not count towards
+/// their time weighted shares
+pub const MEMBER_INACTIVITY: Map<Addr, MemberInactivity> = Map::new("member_inactivity");
+
+/// inactivity record of a single guild member
+#[cw_serde]
+#[derive(Default)]
+pub struct MemberInactivity {
+    /// start of the current inactive period, if the member is inactive
+    pub inactive_since: Option<Timestamp>,
+    /// total length of the past inactive periods since the member start
+    pub inactive_seconds: u64,
+}
+
+/// whether ticks push the splits to receivers or accrue them for claiming
+pub const DISTRIBUTION_MODE: Item<DistributionMode> = Item::new("distribution_mode");
+
+/// (receiver address, denom) -> amount that can be claimed by the receiver.
+/// cw20 tokens are tracked under their `cw20:<token address>` denom
+pub const CLAIMABLE: Map<(String, String), Uint128> = Map::new("claimable");
+
+/// denom -> sum of all claimable amounts. these funds are owed to the
+/// receivers and are excluded from the distributable balances.
+pub const TOTAL_CLAIMABLE: Map<String, Uint128> = Map::new("total_claimable");
+
+/// how rounding remainders of the splits are handled
+pub const REMAINDER_POLICY: Item<RemainderPolicy> = Item::new("remainder_policy");
+
+/// denom -> remainder left in the contract by the split of the last tick
+pub const CARRIED_DUST: Map<String, Uint128> = Map::new("carried_dust");
+
+/// fee attached to IBC transfers to receivers on other chains
+pub const IBC_FEE: Item<IbcFee> = Item::new("ibc_fee");
+
+/// next reply id, so that every IBC transfer submsg gets its own payload
+pub const NEXT_REPLY_ID: Item<u64> = Item::new("next_reply_id");
+
+/// reply id -> IBC transfer that the reply belongs to
+pub const REPLY_PAYLOADS: Map<u64, IbcTransferInfo> = Map::new("reply_payloads");
+
+/// (channel id, sequence) -> IBC transfer awaiting its acknowledgement
+pub const PENDING_IBC_TRANSFERS: Map<(String, u64), IbcTransferInfo> =
+    Map::new("pending_ibc_transfers");
+
+/// id of the next distribution record
+pub const NEXT_DISTRIBUTION_ID: Item<u64> = Item::new("next_distribution_id");
+
+/// distribution id -> record of what was split during that tick
+pub const DISTRIBUTION_HISTORY: Map<u64, DistributionRecord> = Map::new("distribution_history");
+
+/// (receiver address, denom) -> lifetime amount split to the receiver
+pub const RECEIVER_TOTALS: Map<(String, String), Uint128> = Map::new("receiver_totals");
+
+/// denom -> lifetime amount split
+pub const DENOM_TOTALS: Map<String, Uint128> = Map::new("denom_totals");
+
+/// tip paid to the sender of a tick
+pub const KEEPER_TIP: Item<KeeperTip> = Item::new("keeper_tip");
+
+/// name of the neutron cron schedule ticking the splitter
+pub const CRON_SCHEDULE: Item<String> = Item::new("cron_schedule");
+
+/// osmosis swap venue for receivers with a payout denom
+pub const SWAP_CONFIG: Item<SwapConfig> = Item::new("swap_config");
+
+/// (channel id, sequence) -> swap that failed on osmosis
+pub const FAILED_SWAPS: Map<(String, u64), FailedSwap> = Map::new("failed_swaps");
+
+/// split config layout of v1.0.0 where shares were integer percentages
+/// that had to sum up to 100. only used to migrate the stored splits.
+#[cw_serde]
+pub struct LegacySplitConfig {
+    pub receivers: Vec<LegacyReceiver>,
+}
+
+#[cw_serde]
+pub struct LegacyReceiver {
+    pub addr: String,
+    pub share: Uint128,
+}
+
+/// v1.0.0 layout of SPLIT_CONFIG_MAP
+pub const LEGACY_SPLIT_CONFIG_MAP: Map<String, LegacySplitConfig> = Map::new("split_config");
+
+/// v1.0.0 layout of FALLBACK_SPLIT
+pub const LEGACY_FALLBACK_SPLIT: Item<LegacySplitConfig> = Item::new("fallback_split");
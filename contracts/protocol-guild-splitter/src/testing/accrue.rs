@@ -0,0 +1,93 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{coin, coins, Addr, Uint128};
+use cw_multi_test::{AppResponse, Executor};
+
+use crate::msg::{DistributionMode, ExecuteMsg};
+
+use super::{instantiate_msg, split, Suite};
+
+/// splitter crediting 60% of every denom to alice and 40% to bob
+fn accrue_suite() -> Suite {
+    let mut msg = instantiate_msg(vec![]);
+    msg.fallback_split = Some(split(&[("alice", 60), ("bob", 40)]));
+    msg.distribution_mode = Some(DistributionMode::Accrue);
+    Suite::new(msg, vec![coin(1000, "uatom"), coin(500, "untrn")])
+}
+
+fn claim(suite: &mut Suite, addr: &str, denoms: Option<Vec<&str>>) -> AnyResult<AppResponse> {
+    suite.app.execute_contract(
+        Addr::unchecked(addr),
+        suite.splitter.clone(),
+        &ExecuteMsg::Claim {
+            denoms: denoms.map(|denoms| denoms.into_iter().map(str::to_string).collect()),
+        },
+        &[],
+    )
+}
+
+#[test]
+fn accrued_splits_are_claimable() {
+    let mut suite = accrue_suite();
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::zero());
+    assert_eq!(
+        suite.claimable("alice"),
+        vec![coin(600, "uatom"), coin(300, "untrn")]
+    );
+    assert_eq!(
+        suite.claimable("bob"),
+        vec![coin(400, "uatom"), coin(200, "untrn")]
+    );
+
+    // credited funds stay in the contract but are not split again
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(
+        suite.claimable("alice"),
+        vec![coin(600, "uatom"), coin(300, "untrn")]
+    );
+
+    claim(&mut suite, "alice", Some(vec!["uatom"])).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(600));
+    assert_eq!(suite.balance("alice", "untrn"), Uint128::zero());
+    assert_eq!(suite.claimable("alice"), coins(300, "untrn"));
+
+    claim(&mut suite, "alice", None).unwrap();
+    assert_eq!(suite.balance("alice", "untrn"), Uint128::new(300));
+    assert!(suite.claimable("alice").is_empty());
+
+    let err = claim(&mut suite, "alice", None).unwrap_err();
+    assert!(err.root_cause().to_string().contains("nothing to claim"));
+    let err = claim(&mut suite, "carol", Some(vec!["uatom"])).unwrap_err();
+    assert!(err.root_cause().to_string().contains("nothing to claim"));
+
+    claim(&mut suite, "bob", None).unwrap();
+    assert_eq!(suite.balance("bob", "uatom"), Uint128::new(400));
+    assert_eq!(suite.balance("bob", "untrn"), Uint128::new(200));
+    assert!(suite.balance(suite.splitter.as_str(), "uatom").is_zero());
+}
+
+#[test]
+fn switching_to_push_keeps_claimables() {
+    let mut suite = accrue_suite();
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+
+    suite
+        .execute(ExecuteMsg::UpdateDistributionMode {
+            mode: DistributionMode::Push,
+        })
+        .unwrap();
+    suite.mint(coins(100, "uatom"));
+
+    // only the new funds are pushed, the credited ones wait to be claimed
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(60));
+    assert_eq!(suite.balance("bob", "uatom"), Uint128::new(40));
+    assert_eq!(
+        suite.claimable("alice"),
+        vec![coin(600, "uatom"), coin(300, "untrn")]
+    );
+
+    claim(&mut suite, "alice", None).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(660));
+}
@@ -0,0 +1,101 @@
+use cosmwasm_std::{coins, StdResult, Uint128};
+
+use crate::msg::{DenomSplit, ExecuteMsg, QueryMsg, SplitConfig, SplitType};
+
+use super::{instantiate_msg, split, Suite};
+
+const ATOM_TRACE: &str = "transfer/channel-0/uatom";
+const ATOM_IBC_DENOM: &str = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+fn denom_aliases(suite: &Suite) -> Vec<(String, String)> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::DenomAliases {})
+        .unwrap()
+}
+
+fn denom_split(suite: &Suite, denom: &str) -> StdResult<SplitType> {
+    suite.app.wrap().query_wasm_smart(
+        &suite.splitter,
+        &QueryMsg::DenomSplit {
+            denom: denom.to_string(),
+        },
+    )
+}
+
+fn update_split(suite: &mut Suite, denom: &str, split: SplitType) {
+    suite
+        .execute(ExecuteMsg::UpdateSplits {
+            set: vec![DenomSplit {
+                denom: denom.to_string(),
+                split,
+                min_distribution: None,
+            }],
+            remove: vec![],
+        })
+        .unwrap();
+}
+
+#[test]
+fn splits_by_denom_trace() {
+    let msg = instantiate_msg(vec![(ATOM_TRACE, split(&[("alice", 100)]))]);
+    let mut suite = Suite::new(msg, coins(1000, ATOM_IBC_DENOM));
+
+    assert_eq!(
+        denom_aliases(&suite),
+        vec![(ATOM_TRACE.to_string(), ATOM_IBC_DENOM.to_string())]
+    );
+    assert_eq!(
+        denom_split(&suite, ATOM_TRACE).unwrap(),
+        split(&[("alice", 100)])
+    );
+    assert_eq!(
+        denom_split(&suite, ATOM_IBC_DENOM).unwrap(),
+        split(&[("alice", 100)])
+    );
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", ATOM_IBC_DENOM), Uint128::new(1000));
+}
+
+#[test]
+fn replacing_by_hashed_denom_drops_alias() {
+    let msg = instantiate_msg(vec![(ATOM_TRACE, split(&[("alice", 100)]))]);
+    let mut suite = Suite::new(msg, vec![]);
+
+    update_split(&mut suite, ATOM_IBC_DENOM, split(&[("bob", 100)]));
+    assert!(denom_aliases(&suite).is_empty());
+    assert_eq!(
+        denom_split(&suite, ATOM_TRACE).unwrap(),
+        split(&[("bob", 100)])
+    );
+
+    // setting it by its trace again brings the alias back
+    update_split(&mut suite, ATOM_TRACE, split(&[("alice", 100)]));
+    assert_eq!(
+        denom_aliases(&suite),
+        vec![(ATOM_TRACE.to_string(), ATOM_IBC_DENOM.to_string())]
+    );
+}
+
+#[test]
+fn removing_split_drops_alias() {
+    for removed in [ATOM_TRACE, ATOM_IBC_DENOM] {
+        let msg = instantiate_msg(vec![(ATOM_TRACE, split(&[("alice", 100)]))]);
+        let mut suite = Suite::new(msg, vec![]);
+
+        suite
+            .execute(ExecuteMsg::UpdateSplits {
+                set: vec![],
+                remove: vec![removed.to_string()],
+            })
+            .unwrap();
+        assert!(denom_aliases(&suite).is_empty());
+
+        // denoms without a split have no receivers
+        let no_split = SplitType::Custom(SplitConfig { receivers: vec![] });
+        assert_eq!(denom_split(&suite, ATOM_TRACE).unwrap(), no_split);
+        assert_eq!(denom_split(&suite, ATOM_IBC_DENOM).unwrap(), no_split);
+    }
+}
@@ -0,0 +1,100 @@
+use cosmwasm_std::{coin, Decimal, Uint128, Uint64};
+
+use crate::msg::{ExecuteMsg, IbcDestination, Receiver, SplitConfig, SplitType};
+
+use super::{instantiate_msg, SentTransfer, Suite};
+
+const CRON_SCHEDULE: &str = "protocol_guild";
+
+/// splitter paying 60% of uatom to alice on neutron and 40% to bob over IBC
+fn cron_suite(cron_schedule: Option<&str>) -> Suite {
+    let split = SplitType::Custom(SplitConfig {
+        receivers: vec![
+            Receiver {
+                addr: "alice".to_string(),
+                share: Decimal::percent(60),
+                ibc: None,
+                payout_denom: None,
+            },
+            Receiver {
+                addr: "bob".to_string(),
+                share: Decimal::percent(40),
+                ibc: Some(IbcDestination {
+                    channel_id: "channel-5".to_string(),
+                    remote_addr: "cosmos1bob".to_string(),
+                    timeout: Uint64::new(600),
+                }),
+                payout_denom: None,
+            },
+        ],
+    });
+
+    let mut msg = instantiate_msg(vec![("uatom", split)]);
+    msg.cron_schedule = cron_schedule.map(str::to_string);
+
+    // the untrn balance covers the fees of the IBC transfer
+    Suite::new(msg, vec![coin(1000, "uatom"), coin(20_000, "untrn")])
+}
+
+#[test]
+fn cron_tick_distributes() {
+    let mut suite = cron_suite(Some(CRON_SCHEDULE));
+
+    let response = suite.cron_tick().unwrap();
+    assert!(response.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "cron_schedule" && attr.value == CRON_SCHEDULE)));
+
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(600));
+    assert_eq!(
+        suite.ibc_transfers(),
+        vec![SentTransfer {
+            sequence: 1,
+            channel: "channel-5".to_string(),
+            receiver: "cosmos1bob".to_string(),
+            token: coin(400, "uatom"),
+            memo: "".to_string(),
+        }]
+    );
+    assert_eq!(
+        suite.balance(suite.splitter.as_str(), "uatom"),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn cron_tick_requires_schedule() {
+    let mut suite = cron_suite(None);
+
+    let err = suite.cron_tick().unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("no cron schedule configured"));
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::zero());
+
+    // ticking by cron can be enabled later on
+    suite
+        .execute(ExecuteMsg::UpdateCronSchedule {
+            schedule: Some(CRON_SCHEDULE.to_string()),
+        })
+        .unwrap();
+    suite.cron_tick().unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(600));
+}
+
+#[test]
+fn cron_tick_rejected_while_paused() {
+    let mut suite = cron_suite(Some(CRON_SCHEDULE));
+    suite.execute(ExecuteMsg::Pause {}).unwrap();
+
+    let err = suite.cron_tick().unwrap_err();
+    assert!(err.root_cause().to_string().contains("contract is paused"));
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::zero());
+    assert!(suite.ibc_transfers().is_empty());
+
+    suite.execute(ExecuteMsg::Unpause {}).unwrap();
+    suite.cron_tick().unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(600));
+}
@@ -0,0 +1,235 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, Uint64, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use neutron_sdk::bindings::{msg::NeutronMsg, query::NeutronQuery};
+
+use crate::msg::{Cw20Split, ExecuteMsg, IbcDestination, Receiver, SplitConfig, SplitType};
+
+use super::{instantiate_msg, split, Suite, OWNER};
+
+const CW20_BALANCES: Map<Addr, Uint128> = Map::new("balances");
+
+#[cw_serde]
+struct Cw20InstantiateMsg {
+    balances: Vec<(String, Uint128)>,
+}
+
+fn cw20_instantiate(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: Cw20InstantiateMsg,
+) -> StdResult<Response<NeutronMsg>> {
+    for (addr, amount) in msg.balances {
+        CW20_BALANCES.save(deps.storage, Addr::unchecked(addr), &amount)?;
+    }
+    Ok(Response::default())
+}
+
+fn move_balance(
+    deps: DepsMut<NeutronQuery>,
+    from: Addr,
+    to: Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    CW20_BALANCES.update(deps.storage, from, |balance| {
+        balance
+            .unwrap_or_default()
+            .checked_sub(amount)
+            .map_err(StdError::from)
+    })?;
+    CW20_BALANCES.update(deps.storage, to, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default() + amount)
+    })?;
+    Ok(())
+}
+
+/// bare cw20 supporting the transfers and queries of the splitter
+fn cw20_execute(
+    deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+) -> StdResult<Response<NeutronMsg>> {
+    match msg {
+        Cw20ExecuteMsg::Transfer { recipient, amount } => {
+            move_balance(deps, info.sender, Addr::unchecked(recipient), amount)?;
+            Ok(Response::default())
+        }
+        Cw20ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => {
+            move_balance(
+                deps,
+                info.sender.clone(),
+                Addr::unchecked(&contract),
+                amount,
+            )?;
+            let receive_msg = Cw20ReceiveMsg {
+                sender: info.sender.to_string(),
+                amount,
+                msg,
+            };
+            Ok(Response::default().add_message(WasmMsg::Execute {
+                contract_addr: contract,
+                msg: receive_msg.into_binary()?,
+                funds: vec![],
+            }))
+        }
+        _ => Err(StdError::generic_err("unsupported cw20 msg")),
+    }
+}
+
+fn cw20_query(deps: Deps<NeutronQuery>, _env: Env, msg: Cw20QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw20QueryMsg::Balance { address } => to_binary(&BalanceResponse {
+            balance: CW20_BALANCES
+                .may_load(deps.storage, Addr::unchecked(address))?
+                .unwrap_or_default(),
+        }),
+        _ => Err(StdError::generic_err("unsupported cw20 query")),
+    }
+}
+
+fn cw20_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    Box::new(ContractWrapper::new(
+        cw20_execute,
+        cw20_instantiate,
+        cw20_query,
+    ))
+}
+
+/// instantiates a cw20 token with the given amount held by the owner
+fn create_token(suite: &mut Suite, amount: u128) -> Addr {
+    let code_id = suite.app.store_code(cw20_contract());
+    suite
+        .app
+        .instantiate_contract(
+            code_id,
+            suite.owner.clone(),
+            &Cw20InstantiateMsg {
+                balances: vec![(OWNER.to_string(), Uint128::new(amount))],
+            },
+            &[],
+            "token",
+            None,
+        )
+        .unwrap()
+}
+
+fn cw20_balance(suite: &Suite, token: &Addr, addr: &str) -> Uint128 {
+    let response: BalanceResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: addr.to_string(),
+            },
+        )
+        .unwrap();
+    response.balance
+}
+
+/// sends the amount of the token from the owner to the splitter
+fn send_to_splitter(suite: &mut Suite, token: &Addr, amount: u128) -> AnyResult<()> {
+    suite.app.execute_contract(
+        suite.owner.clone(),
+        token.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: suite.splitter.to_string(),
+            amount: Uint128::new(amount),
+            msg: Binary::default(),
+        },
+        &[],
+    )?;
+    Ok(())
+}
+
+fn allowlist(suite: &mut Suite, token: &Addr, split: SplitType) {
+    suite
+        .execute(ExecuteMsg::UpdateCw20Splits {
+            set: vec![Cw20Split {
+                token: token.to_string(),
+                split,
+            }],
+            remove: vec![],
+        })
+        .unwrap();
+}
+
+#[test]
+fn rejects_tokens_that_are_not_allowlisted() {
+    let mut suite = Suite::new(instantiate_msg(vec![]), vec![]);
+    let token = create_token(&mut suite, 1000);
+
+    let err = send_to_splitter(&mut suite, &token, 1000).unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("cw20 token is not allowlisted"));
+    assert_eq!(cw20_balance(&suite, &token, OWNER), Uint128::new(1000));
+}
+
+#[test]
+fn distributes_cw20_with_transfers() {
+    let mut suite = Suite::new(instantiate_msg(vec![]), vec![]);
+    let token = create_token(&mut suite, 1000);
+    allowlist(&mut suite, &token, split(&[("alice", 60), ("bob", 40)]));
+
+    send_to_splitter(&mut suite, &token, 1000).unwrap();
+    assert_eq!(
+        cw20_balance(&suite, &token, suite.splitter.as_str()),
+        Uint128::new(1000)
+    );
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(cw20_balance(&suite, &token, "alice"), Uint128::new(600));
+    assert_eq!(cw20_balance(&suite, &token, "bob"), Uint128::new(400));
+    assert!(cw20_balance(&suite, &token, suite.splitter.as_str()).is_zero());
+}
+
+#[test]
+fn cw20_splits_do_not_reserve_ibc_fees() {
+    // the splitter holds no untrn to pay IBC fees with
+    let mut suite = Suite::new(instantiate_msg(vec![]), vec![]);
+    let token = create_token(&mut suite, 1000);
+    let ibc_split = SplitType::Custom(SplitConfig {
+        receivers: vec![
+            Receiver {
+                addr: "alice".to_string(),
+                share: Decimal::percent(50),
+                ibc: None,
+                payout_denom: None,
+            },
+            Receiver {
+                addr: "bob".to_string(),
+                share: Decimal::percent(50),
+                ibc: Some(IbcDestination {
+                    channel_id: "channel-5".to_string(),
+                    remote_addr: "cosmos1bob".to_string(),
+                    timeout: Uint64::new(600),
+                }),
+                payout_denom: None,
+            },
+        ],
+    });
+    allowlist(&mut suite, &token, ibc_split);
+    send_to_splitter(&mut suite, &token, 1000).unwrap();
+
+    // cw20 tokens can not be sent over IBC, so both receivers are paid
+    // on neutron without any fees
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert!(suite.ibc_transfers().is_empty());
+    assert_eq!(cw20_balance(&suite, &token, "alice"), Uint128::new(500));
+    assert_eq!(cw20_balance(&suite, &token, "bob"), Uint128::new(500));
+    assert!(suite.claimable("bob").is_empty());
+}
@@ -0,0 +1,100 @@
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128, Uint64};
+use cw_multi_test::Executor;
+
+use crate::msg::{
+    DistributionMode, ExecuteMsg, IbcDestination, IbcTransferInfo, QueryMsg, Receiver, SplitConfig,
+    SplitType,
+};
+
+use super::{instantiate_msg, split, Suite};
+
+const RECOVERY: &str = "recovery";
+
+fn pending_ibc_transfers(suite: &Suite) -> Vec<((String, u64), IbcTransferInfo)> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::PendingIbcTransfers {})
+        .unwrap()
+}
+
+fn emergency_withdraw(suite: &mut Suite) {
+    suite
+        .execute(ExecuteMsg::EmergencyWithdraw {
+            recovery_address: RECOVERY.to_string(),
+        })
+        .unwrap();
+}
+
+#[test]
+fn emergency_withdraw_forgets_pending_transfers() {
+    let split = SplitType::Custom(SplitConfig {
+        receivers: vec![Receiver {
+            addr: "bob".to_string(),
+            share: Decimal::one(),
+            ibc: Some(IbcDestination {
+                channel_id: "channel-5".to_string(),
+                remote_addr: "cosmos1bob".to_string(),
+                timeout: Uint64::new(600),
+            }),
+            payout_denom: None,
+        }],
+    });
+    let msg = instantiate_msg(vec![("uatom", split)]);
+    let mut suite = Suite::new(msg, vec![coin(1000, "uatom"), coin(25_000, "untrn")]);
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    let transfers = suite.ibc_transfers();
+    assert_eq!(pending_ibc_transfers(&suite).len(), 1);
+
+    // the untrn left after escrowing the fees is recovered
+    emergency_withdraw(&mut suite);
+    assert_eq!(suite.balance(RECOVERY, "untrn"), Uint128::new(5000));
+    assert!(pending_ibc_transfers(&suite).is_empty());
+
+    // the refund is not credited to bob and can be recovered as well
+    suite.fail_transfer(&transfers[0], None);
+    assert!(suite.claimable("bob").is_empty());
+    emergency_withdraw(&mut suite);
+    assert_eq!(suite.balance(RECOVERY, "uatom"), Uint128::new(1000));
+    assert_eq!(
+        suite.balance(suite.splitter.as_str(), "uatom"),
+        Uint128::zero()
+    );
+}
+
+#[test]
+fn claimable_balances_survive_emergency_withdraw() {
+    let mut msg = instantiate_msg(vec![("uatom", split(&[("alice", 60), ("bob", 40)]))]);
+    msg.distribution_mode = Some(DistributionMode::Accrue);
+    let mut suite = Suite::new(msg, coins(1000, "uatom"));
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    suite.mint(coins(250, "uatom"));
+
+    // only the balance that is not owed to anyone is recovered
+    emergency_withdraw(&mut suite);
+    assert_eq!(suite.balance(RECOVERY, "uatom"), Uint128::new(250));
+    assert_eq!(suite.claimable("alice"), coins(600, "uatom"));
+    assert_eq!(suite.claimable("bob"), coins(400, "uatom"));
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            suite.splitter.clone(),
+            &ExecuteMsg::Claim { denoms: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(600));
+
+    // bob's split is all that is left
+    let err = suite
+        .execute(ExecuteMsg::EmergencyWithdraw {
+            recovery_address: RECOVERY.to_string(),
+        })
+        .unwrap_err();
+    assert!(err.root_cause().to_string().contains("nothing to withdraw"));
+    assert_eq!(suite.claimable("bob"), coins(400, "uatom"));
+}
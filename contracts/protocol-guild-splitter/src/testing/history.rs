@@ -0,0 +1,96 @@
+use cosmwasm_std::{coin, coins, Coin, Uint128};
+
+use crate::msg::{DenomDistribution, DistributionRecord, ExecuteMsg, QueryMsg};
+
+use super::{instantiate_msg, split, Suite};
+
+fn history(suite: &Suite, start_after: Option<u64>, limit: Option<u32>) -> Vec<DistributionRecord> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.splitter,
+            &QueryMsg::DistributionHistory { start_after, limit },
+        )
+        .unwrap()
+}
+
+fn history_ids(records: &[DistributionRecord]) -> Vec<u64> {
+    records.iter().map(|record| record.id).collect()
+}
+
+#[test]
+fn records_distributions_and_totals() {
+    let msg = instantiate_msg(vec![("uatom", split(&[("alice", 60), ("bob", 40)]))]);
+    let mut suite = Suite::new(msg, coins(1000, "uatom"));
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    let block = suite.app.block_info();
+    assert_eq!(
+        history(&suite, None, None),
+        vec![DistributionRecord {
+            id: 0,
+            height: block.height,
+            time: block.time,
+            distributions: vec![DenomDistribution {
+                denom: "uatom".to_string(),
+                total: Uint128::new(1000),
+                receivers: vec![
+                    ("alice".to_string(), Uint128::new(600)),
+                    ("bob".to_string(), Uint128::new(400)),
+                ],
+            }],
+        }]
+    );
+
+    // ticks that split nothing are not recorded
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(history_ids(&history(&suite, None, None)), vec![0]);
+
+    suite.mint(coins(500, "uatom"));
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(history_ids(&history(&suite, None, None)), vec![0, 1]);
+
+    let receiver_totals: Vec<Coin> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.splitter,
+            &QueryMsg::ReceiverTotals {
+                addr: "alice".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(receiver_totals, coins(900, "uatom"));
+
+    let denom_totals: Vec<Coin> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::DenomTotals {})
+        .unwrap();
+    assert_eq!(denom_totals, coins(1500, "uatom"));
+}
+
+#[test]
+fn pages_distribution_history() {
+    let msg = instantiate_msg(vec![("uatom", split(&[("alice", 100)]))]);
+    let mut suite = Suite::new(msg, vec![]);
+    for _ in 0..35 {
+        suite.mint(vec![coin(10, "uatom")]);
+        suite.execute(ExecuteMsg::Tick {}).unwrap();
+    }
+
+    // 10 records by default and never more than 30
+    assert_eq!(
+        history_ids(&history(&suite, None, None)),
+        (0..10).collect::<Vec<u64>>()
+    );
+    assert_eq!(
+        history_ids(&history(&suite, None, Some(50))),
+        (0..30).collect::<Vec<u64>>()
+    );
+    assert_eq!(
+        history_ids(&history(&suite, Some(29), Some(50))),
+        (30..35).collect::<Vec<u64>>()
+    );
+}
@@ -0,0 +1,150 @@
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128, Uint64};
+use cw_multi_test::Executor;
+use neutron_sdk::bindings::msg::IbcFee;
+
+use crate::msg::{ExecuteMsg, IbcDestination, QueryMsg, Receiver, SplitConfig, SplitType};
+
+use super::{instantiate_msg, min_ibc_fee, splitter_contract, Suite, IBC_ESCROW};
+
+/// splitter paying the given percent of uatom to alice on neutron and
+/// the rest to bob over IBC
+fn ibc_split(local_percent: u64) -> SplitType {
+    SplitType::Custom(SplitConfig {
+        receivers: vec![
+            Receiver {
+                addr: "alice".to_string(),
+                share: Decimal::percent(local_percent),
+                ibc: None,
+                payout_denom: None,
+            },
+            Receiver {
+                addr: "bob".to_string(),
+                share: Decimal::percent(100 - local_percent),
+                ibc: Some(IbcDestination {
+                    channel_id: "channel-5".to_string(),
+                    remote_addr: "cosmos1bob".to_string(),
+                    timeout: Uint64::new(600),
+                }),
+                payout_denom: None,
+            },
+        ],
+    })
+}
+
+fn ibc_fee(suite: &Suite) -> IbcFee {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::IbcFee {})
+        .unwrap()
+}
+
+#[test]
+fn defaults_to_min_ibc_fee() {
+    let suite = Suite::new(instantiate_msg(vec![("uatom", ibc_split(60))]), vec![]);
+    assert_eq!(ibc_fee(&suite), min_ibc_fee());
+}
+
+#[test]
+fn rejects_fees_below_min_ibc_fee() {
+    let mut suite = Suite::new(instantiate_msg(vec![("uatom", ibc_split(60))]), vec![]);
+
+    let below_min = IbcFee {
+        recv_fee: vec![],
+        ack_fee: coins(10000, "untrn"),
+        timeout_fee: coins(9999, "untrn"),
+    };
+    let with_recv_fee = IbcFee {
+        recv_fee: coins(1, "untrn"),
+        ..min_ibc_fee()
+    };
+    for fee in [below_min, with_recv_fee] {
+        let err = suite
+            .execute(ExecuteMsg::UpdateIbcFee {
+                ibc_fee: fee.clone(),
+            })
+            .unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("cover the minimum ack and timeout fees"));
+
+        let mut msg = instantiate_msg(vec![("uatom", ibc_split(60))]);
+        msg.ibc_fee = Some(fee);
+        let code_id = suite.app.store_code(splitter_contract());
+        suite
+            .app
+            .instantiate_contract(code_id, suite.owner.clone(), &msg, &[], "splitter", None)
+            .unwrap_err();
+    }
+    assert_eq!(ibc_fee(&suite), min_ibc_fee());
+
+    let above_min = IbcFee {
+        recv_fee: vec![],
+        ack_fee: coins(20000, "untrn"),
+        timeout_fee: coins(10000, "untrn"),
+    };
+    suite
+        .execute(ExecuteMsg::UpdateIbcFee {
+            ibc_fee: above_min.clone(),
+        })
+        .unwrap();
+    assert_eq!(ibc_fee(&suite), above_min);
+}
+
+#[test]
+fn reserves_fees_of_sent_transfers_only() {
+    // bob's 1% of 50uatom rounds down to nothing, so no transfer is sent
+    // and no fees have to be held back
+    let msg = instantiate_msg(vec![("uatom", ibc_split(99))]);
+    let mut suite = Suite::new(msg, coins(50, "uatom"));
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(49));
+    assert!(suite.ibc_transfers().is_empty());
+
+    // once bob is paid the fees of his transfer are held back
+    suite.mint(vec![coin(100, "uatom"), coin(20000, "untrn")]);
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.ibc_transfers().len(), 1);
+    assert_eq!(suite.ibc_transfers()[0].token, coin(1, "uatom"));
+    assert_eq!(suite.balance(IBC_ESCROW, "untrn"), Uint128::new(20000));
+}
+
+#[test]
+fn credits_ibc_receivers_if_fees_are_short() {
+    // the transfer to bob requires 20000untrn of fees
+    let msg = instantiate_msg(vec![("uatom", ibc_split(60))]);
+    let mut suite = Suite::new(msg, vec![coin(1000, "uatom"), coin(10000, "untrn")]);
+
+    // alice is still paid, bob is credited instead of failing the tick
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(600));
+    assert!(suite.ibc_transfers().is_empty());
+    assert_eq!(suite.claimable("bob"), coins(400, "uatom"));
+    assert_eq!(
+        suite.balance(suite.splitter.as_str(), "untrn"),
+        Uint128::new(10000)
+    );
+
+    // credited funds are held back from the next tick, which can pay
+    // the fees of the transfer again
+    suite.mint(vec![coin(1000, "uatom"), coin(10000, "untrn")]);
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(1200));
+    assert_eq!(suite.ibc_transfers().len(), 1);
+    assert_eq!(suite.ibc_transfers()[0].token, coin(400, "uatom"));
+
+    // bob claims his credit on neutron
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("bob"),
+            suite.splitter.clone(),
+            &ExecuteMsg::Claim { denoms: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.balance("bob", "uatom"), Uint128::new(400));
+    assert!(suite.claimable("bob").is_empty());
+}
@@ -0,0 +1,147 @@
+use cosmwasm_std::{coin, coins, Addr, Uint128, Uint64};
+use cw_multi_test::{AppResponse, Executor};
+
+use crate::msg::{DenomSplit, ExecuteMsg, KeeperTip, KeeperTipResponse, QueryMsg, SplitType};
+
+use super::{instantiate_msg, split, Suite};
+
+const KEEPER: &str = "keeper";
+
+fn keeper_tip(bps: u16, min_interval: u64) -> KeeperTip {
+    KeeperTip {
+        bps,
+        min_interval: Uint64::new(min_interval),
+    }
+}
+
+fn keeper_tick(suite: &mut Suite) -> AppResponse {
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(KEEPER),
+            suite.splitter.clone(),
+            &ExecuteMsg::Tick {},
+            &[],
+        )
+        .unwrap()
+}
+
+fn query_keeper_tip(suite: &Suite) -> KeeperTipResponse {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::KeeperTip {})
+        .unwrap()
+}
+
+#[test]
+fn tips_at_most_once_per_interval() {
+    let mut msg = instantiate_msg(vec![("uatom", split(&[("alice", 100)]))]);
+    msg.keeper_tip = Some(keeper_tip(100, 60));
+    let mut suite = Suite::new(msg, coins(1000, "uatom"));
+
+    keeper_tick(&mut suite);
+    assert_eq!(suite.balance(KEEPER, "uatom"), Uint128::new(10));
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(990));
+    let paid_at = suite.app.block_info().time;
+    assert_eq!(query_keeper_tip(&suite).last_paid_tick, Some(paid_at));
+
+    // too early for another tip, the whole balance is split
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(59);
+    });
+    suite.mint(coins(1000, "uatom"));
+    keeper_tick(&mut suite);
+    assert_eq!(suite.balance(KEEPER, "uatom"), Uint128::new(10));
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(1990));
+    assert_eq!(query_keeper_tip(&suite).last_paid_tick, Some(paid_at));
+
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(1);
+    });
+    suite.mint(coins(1000, "uatom"));
+    keeper_tick(&mut suite);
+    assert_eq!(suite.balance(KEEPER, "uatom"), Uint128::new(20));
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(2980));
+    assert_eq!(
+        query_keeper_tip(&suite).last_paid_tick,
+        Some(suite.app.block_info().time)
+    );
+}
+
+#[test]
+fn cron_ticks_are_not_tipped() {
+    let mut msg = instantiate_msg(vec![("uatom", split(&[("alice", 100)]))]);
+    msg.keeper_tip = Some(keeper_tip(100, 60));
+    msg.cron_schedule = Some("protocol_guild".to_string());
+    let mut suite = Suite::new(msg, coins(1000, "uatom"));
+
+    suite.cron_tick().unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(1000));
+    assert_eq!(query_keeper_tip(&suite).last_paid_tick, None);
+}
+
+#[test]
+fn splits_without_receivers_are_not_tipped() {
+    // nobody is registered for the time weighted split yet
+    let mut msg = instantiate_msg(vec![("uatom", SplitType::TimeWeighted {})]);
+    msg.keeper_tip = Some(keeper_tip(100, 60));
+    let mut suite = Suite::new(msg, coins(1000, "uatom"));
+
+    keeper_tick(&mut suite);
+    assert_eq!(suite.balance(KEEPER, "uatom"), Uint128::zero());
+    assert_eq!(
+        suite.balance(suite.splitter.as_str(), "uatom"),
+        Uint128::new(1000)
+    );
+    assert_eq!(query_keeper_tip(&suite).last_paid_tick, None);
+
+    // only the split that pays someone is tipped
+    suite
+        .execute(ExecuteMsg::UpdateSplits {
+            set: vec![DenomSplit {
+                denom: "untrn".to_string(),
+                split: split(&[("alice", 100)]),
+                min_distribution: None,
+            }],
+            remove: vec![],
+        })
+        .unwrap();
+    suite.mint(coins(1000, "untrn"));
+    keeper_tick(&mut suite);
+    assert_eq!(suite.balance(KEEPER, "untrn"), Uint128::new(10));
+    assert_eq!(suite.balance(KEEPER, "uatom"), Uint128::zero());
+    assert_eq!(suite.balance("alice", "untrn"), Uint128::new(990));
+    assert_eq!(
+        query_keeper_tip(&suite).last_paid_tick,
+        Some(suite.app.block_info().time)
+    );
+}
+
+#[test]
+fn rejects_invalid_keeper_tips() {
+    let mut suite = Suite::new(instantiate_msg(vec![]), vec![coin(1000, "uatom")]);
+
+    for keeper_tip in [keeper_tip(100, 0), keeper_tip(0, 60), keeper_tip(1_001, 60)] {
+        let err = suite
+            .execute(ExecuteMsg::UpdateKeeperTip {
+                keeper_tip: Some(keeper_tip),
+            })
+            .unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("keeper tip must be between 1 and 1000 bps with a non-zero min interval"));
+    }
+    assert_eq!(query_keeper_tip(&suite).keeper_tip, None);
+
+    suite
+        .execute(ExecuteMsg::UpdateKeeperTip {
+            keeper_tip: Some(keeper_tip(1_000, 60)),
+        })
+        .unwrap();
+    assert_eq!(
+        query_keeper_tip(&suite).keeper_tip,
+        Some(keeper_tip(1_000, 60))
+    );
+}
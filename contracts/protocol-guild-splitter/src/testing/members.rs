@@ -0,0 +1,165 @@
+use cosmwasm_std::{Decimal, Timestamp, Uint128};
+
+use crate::msg::{ExecuteMsg, Member, QueryMsg, SplitConfig, SplitType, MAX_MEMBER_WEIGHT};
+
+use super::{instantiate_msg, Suite};
+
+fn member(addr: &str, start: Timestamp, weight: u128, active: bool) -> Member {
+    Member {
+        addr: addr.to_string(),
+        start,
+        weight: Uint128::new(weight),
+        active,
+    }
+}
+
+fn time_weighted_split(suite: &Suite) -> Vec<(String, Decimal)> {
+    let config: SplitConfig = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::TimeWeightedSplit {})
+        .unwrap();
+    config
+        .receivers
+        .into_iter()
+        .map(|r| (r.addr, r.share))
+        .collect()
+}
+
+#[test]
+fn time_weighted_split_normalises_shares() {
+    let now = cosmwasm_std::testing::mock_env().block.time;
+    let mut msg = instantiate_msg(vec![("uatom", SplitType::TimeWeighted {})]);
+    msg.members = vec![
+        member("alice", now.minus_seconds(300), 100, true),
+        member("bob", now.minus_seconds(300), 50, true),
+        member("carol", now.plus_seconds(100), 100, true),
+        member("dave", now.minus_seconds(300), 100, false),
+    ];
+    let mut suite = Suite::new(msg, vec![]);
+    assert_eq!(suite.app.block_info().time, now);
+
+    // carol has not started yet and dave is inactive
+    assert_eq!(
+        time_weighted_split(&suite),
+        vec![
+            ("alice".to_string(), Decimal::from_ratio(2u128, 3u128)),
+            ("bob".to_string(), Decimal::from_ratio(1u128, 3u128)),
+        ]
+    );
+
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(200);
+    });
+
+    // 500s * 100, 500s * 50 and 100s * 100 out of 85000
+    let split = time_weighted_split(&suite);
+    assert_eq!(
+        split,
+        vec![
+            ("alice".to_string(), Decimal::from_ratio(10u128, 17u128)),
+            ("bob".to_string(), Decimal::from_ratio(5u128, 17u128)),
+            ("carol".to_string(), Decimal::from_ratio(2u128, 17u128)),
+        ]
+    );
+
+    // rounding down never hands out more than the full amount
+    let total: Decimal = split.iter().map(|(_, share)| *share).sum();
+    assert!(total <= Decimal::one());
+    assert!(Decimal::one() - total < Decimal::raw(split.len() as u128));
+}
+
+#[test]
+fn rejects_unbounded_weights() {
+    let now = cosmwasm_std::testing::mock_env().block.time;
+    let mut suite = Suite::new(
+        instantiate_msg(vec![("uatom", SplitType::TimeWeighted {})]),
+        vec![],
+    );
+
+    for weight in [0, MAX_MEMBER_WEIGHT + 1, u128::MAX] {
+        let err = suite
+            .execute(ExecuteMsg::UpdateMembers {
+                set: vec![member("alice", now, weight, true)],
+                remove: vec![],
+            })
+            .unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("misconfigured guild member"));
+    }
+
+    suite
+        .execute(ExecuteMsg::UpdateMembers {
+            set: vec![member("alice", now, MAX_MEMBER_WEIGHT, true)],
+            remove: vec![],
+        })
+        .unwrap();
+}
+
+#[test]
+fn inactive_periods_do_not_count() {
+    let now = cosmwasm_std::testing::mock_env().block.time;
+    let start = now.minus_seconds(300);
+    let mut msg = instantiate_msg(vec![("uatom", SplitType::TimeWeighted {})]);
+    msg.members = vec![
+        member("alice", start, 100, true),
+        member("bob", start, 100, true),
+    ];
+    let mut suite = Suite::new(msg, vec![]);
+
+    let set_bob_active = |suite: &mut Suite, active: bool| {
+        suite
+            .execute(ExecuteMsg::UpdateMembers {
+                set: vec![member("bob", start, 100, active)],
+                remove: vec![],
+            })
+            .unwrap();
+    };
+
+    set_bob_active(&mut suite, false);
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(200);
+    });
+    assert_eq!(
+        time_weighted_split(&suite),
+        vec![("alice".to_string(), Decimal::one())]
+    );
+
+    set_bob_active(&mut suite, true);
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(100);
+    });
+
+    // 600s for alice and 400s for bob, who was inactive for 200s
+    assert_eq!(
+        time_weighted_split(&suite),
+        vec![
+            ("alice".to_string(), Decimal::from_ratio(3u128, 5u128)),
+            ("bob".to_string(), Decimal::from_ratio(2u128, 5u128)),
+        ]
+    );
+
+    // updating the member keeps its inactive periods
+    set_bob_active(&mut suite, true);
+    assert_eq!(
+        time_weighted_split(&suite)[1],
+        ("bob".to_string(), Decimal::from_ratio(2u128, 5u128))
+    );
+
+    // while a new start forgets them, 600s for alice and 500s for bob
+    suite
+        .execute(ExecuteMsg::UpdateMembers {
+            set: vec![member("bob", start.plus_seconds(100), 100, true)],
+            remove: vec![],
+        })
+        .unwrap();
+    assert_eq!(
+        time_weighted_split(&suite),
+        vec![
+            ("alice".to_string(), Decimal::from_ratio(6u128, 11u128)),
+            ("bob".to_string(), Decimal::from_ratio(5u128, 11u128)),
+        ]
+    );
+}
@@ -0,0 +1,141 @@
+use std::marker::PhantomData;
+
+use cosmwasm_std::{
+    testing::{mock_env, MockApi, MockQuerier, MockStorage},
+    to_binary, ContractResult, OwnedDeps, SystemError, SystemResult, Uint128,
+};
+use cw2::{get_contract_version, set_contract_version};
+use neutron_sdk::bindings::query::{MinIbcFeeResponse, NeutronQuery};
+
+use crate::{
+    contract::migrate,
+    error::ContractError,
+    msg::MigrateMsg,
+    state::{
+        LegacyReceiver, LegacySplitConfig, FALLBACK_SPLIT, IBC_FEE, LEGACY_FALLBACK_SPLIT,
+        LEGACY_SPLIT_CONFIG_MAP, SPLIT_CONFIG_MAP,
+    },
+};
+use guild_interfaces::admin::{AdminError, OWNER};
+
+use super::{min_ibc_fee, split};
+
+const CONTRACT_NAME: &str = "crates.io:protocol-guild-splitter";
+
+type NeutronDeps = OwnedDeps<MockStorage, MockApi, MockQuerier<NeutronQuery>, NeutronQuery>;
+
+/// deps answering the feerefunder minimum fee query
+fn neutron_deps() -> NeutronDeps {
+    let querier = MockQuerier::new(&[]).with_custom_handler(|query| match query {
+        NeutronQuery::MinIbcFee {} => SystemResult::Ok(ContractResult::Ok(
+            to_binary(&MinIbcFeeResponse {
+                min_fee: min_ibc_fee(),
+            })
+            .unwrap(),
+        )),
+        _ => SystemResult::Err(SystemError::UnsupportedRequest {
+            kind: format!("{query:?}"),
+        }),
+    });
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier,
+        custom_query_type: PhantomData,
+    }
+}
+
+fn legacy_split(receivers: &[(&str, u128)]) -> LegacySplitConfig {
+    LegacySplitConfig {
+        receivers: receivers
+            .iter()
+            .map(|(addr, share)| LegacyReceiver {
+                addr: addr.to_string(),
+                share: Uint128::new(*share),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn migrates_from_v1_0_0() {
+    let mut deps = neutron_deps();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "1.0.0").unwrap();
+    LEGACY_SPLIT_CONFIG_MAP
+        .save(
+            deps.as_mut().storage,
+            "untrn".to_string(),
+            &legacy_split(&[("alice", 60), ("bob", 40), ("carol", 0)]),
+        )
+        .unwrap();
+    LEGACY_FALLBACK_SPLIT
+        .save(deps.as_mut().storage, &legacy_split(&[("alice", 100)]))
+        .unwrap();
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            owner: Some("owner".to_string()),
+        },
+    )
+    .unwrap();
+
+    // zero shares are dropped along the way
+    assert_eq!(
+        SPLIT_CONFIG_MAP
+            .load(deps.as_ref().storage, "untrn".to_string())
+            .unwrap(),
+        split(&[("alice", 60), ("bob", 40)])
+    );
+    assert_eq!(
+        FALLBACK_SPLIT.load(deps.as_ref().storage).unwrap(),
+        split(&[("alice", 100)])
+    );
+    assert_eq!(IBC_FEE.load(deps.as_ref().storage).unwrap(), min_ibc_fee());
+    assert_eq!(OWNER.load(deps.as_ref().storage).unwrap().as_str(), "owner");
+
+    let version = get_contract_version(deps.as_ref().storage).unwrap();
+    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn rejects_oversized_legacy_shares() {
+    let mut deps = neutron_deps();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "1.0.0").unwrap();
+    LEGACY_SPLIT_CONFIG_MAP
+        .save(
+            deps.as_mut().storage,
+            "untrn".to_string(),
+            &legacy_split(&[("alice", u64::MAX as u128 + 1)]),
+        )
+        .unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { owner: None }).unwrap_err();
+    assert!(matches!(err, ContractError::SplitMisconfig {}));
+}
+
+#[test]
+fn rejects_other_contracts() {
+    let mut deps = neutron_deps();
+    set_contract_version(deps.as_mut().storage, "crates.io:ibc-forwarder", "1.0.0").unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { owner: None }).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Admin(AdminError::InvalidMigration { .. })
+    ));
+}
+
+#[test]
+fn rejects_newer_versions() {
+    let mut deps = neutron_deps();
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { owner: None }).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Admin(AdminError::InvalidMigration { .. })
+    ));
+}
@@ -0,0 +1,84 @@
+use cosmwasm_std::{coin, coins, Coin, Uint128};
+
+use crate::msg::{ExecuteMsg, QueryMsg};
+
+use super::{instantiate_msg, split, Suite};
+
+fn carried_dust(suite: &Suite) -> Vec<Coin> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::CarriedDust {})
+        .unwrap()
+}
+
+fn min_distribution(suite: &Suite, denom: &str) -> Uint128 {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.splitter,
+            &QueryMsg::MinDistribution {
+                denom: denom.to_string(),
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn waits_for_min_distribution() {
+    let mut msg = instantiate_msg(vec![("uatom", split(&[("alice", 60), ("bob", 40)]))]);
+    msg.splits[0].min_distribution = Some(Uint128::new(1000));
+    msg.fallback_split = Some(split(&[("alice", 100)]));
+    msg.fallback_min_distribution = Some(Uint128::new(50));
+    let mut suite = Suite::new(msg, vec![coin(999, "uatom"), coin(40, "uosmo")]);
+
+    assert_eq!(min_distribution(&suite, "uatom"), Uint128::new(1000));
+    assert_eq!(min_distribution(&suite, "uosmo"), Uint128::new(50));
+
+    // neither balance reached its minimum yet
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::zero());
+    assert_eq!(suite.balance("alice", "uosmo"), Uint128::zero());
+
+    suite.mint(coins(1, "uatom"));
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(600));
+    assert_eq!(suite.balance("bob", "uatom"), Uint128::new(400));
+    assert_eq!(suite.balance("alice", "uosmo"), Uint128::zero());
+
+    suite.mint(coins(10, "uosmo"));
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uosmo"), Uint128::new(50));
+}
+
+#[test]
+fn skips_zero_amount_sends() {
+    let msg = instantiate_msg(vec![("uatom", split(&[("alice", 99), ("bob", 1)]))]);
+    let mut suite = Suite::new(msg, coins(50, "uatom"));
+
+    // bob's share rounds down to nothing, which must not fail the tick
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(49));
+    assert_eq!(suite.balance("bob", "uatom"), Uint128::zero());
+}
+
+#[test]
+fn skipped_balances_carry_no_dust() {
+    let mut msg = instantiate_msg(vec![("uatom", split(&[("alice", 50), ("bob", 50)]))]);
+    msg.splits[0].min_distribution = Some(Uint128::new(100));
+    let mut suite = Suite::new(msg, coins(101, "uatom"));
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(carried_dust(&suite), vec![coin(1, "uatom")]);
+
+    // the remainder alone is below the minimum, so nothing is split and
+    // no dust is carried over from this tick
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(50));
+    assert!(carried_dust(&suite).is_empty());
+    assert_eq!(
+        suite.balance(suite.splitter.as_str(), "uatom"),
+        Uint128::new(1)
+    );
+}
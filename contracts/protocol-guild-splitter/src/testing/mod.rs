@@ -0,0 +1,220 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{coins, Addr, Binary, Coin, Decimal, Uint128};
+use cw_multi_test::{AppResponse, BankSudo, Contract, ContractWrapper, Executor};
+use guild_interfaces::cron::CronSudoMsg;
+use guild_testing::{
+    ibc_transfers, neutron_app, NeutronApp, NeutronMock, SentTransfer, IBC_ESCROW,
+};
+use neutron_sdk::{
+    bindings::{
+        msg::{IbcFee, NeutronMsg},
+        query::NeutronQuery,
+    },
+    sudo::msg::{RequestPacket, SudoMsg},
+};
+
+use crate::{
+    contract::{execute, instantiate, migrate, query, reply, sudo},
+    msg::{DenomSplit, ExecuteMsg, InstantiateMsg, QueryMsg, Receiver, SplitConfig, SplitType},
+};
+
+mod accrue;
+mod aliases;
+mod cron;
+mod cw20_splits;
+mod emergency;
+mod history;
+mod ibc_fee;
+mod keeper;
+mod members;
+mod migrate;
+mod min_distribution;
+mod ownership;
+mod remainder;
+mod swap;
+
+const OWNER: &str = "owner";
+
+/// minimum fee required by the feerefunder module
+fn min_ibc_fee() -> IbcFee {
+    IbcFee {
+        recv_fee: vec![],
+        ack_fee: coins(10000, "untrn"),
+        timeout_fee: coins(10000, "untrn"),
+    }
+}
+
+fn splitter_contract() -> Box<dyn Contract<NeutronMsg, NeutronQuery>> {
+    Box::new(
+        ContractWrapper::new(execute, instantiate, query)
+            .with_sudo(sudo)
+            .with_reply_empty(reply)
+            .with_migrate_empty(migrate),
+    )
+}
+
+/// custom split between the given (address, percent) pairs
+fn split(receivers: &[(&str, u64)]) -> SplitType {
+    SplitType::Custom(SplitConfig {
+        receivers: receivers
+            .iter()
+            .map(|(addr, percent)| Receiver {
+                addr: addr.to_string(),
+                share: Decimal::percent(*percent),
+                ibc: None,
+                payout_denom: None,
+            })
+            .collect(),
+    })
+}
+
+/// splitter with the given denom splits and nothing else configured
+fn instantiate_msg(splits: Vec<(&str, SplitType)>) -> InstantiateMsg {
+    InstantiateMsg {
+        splits: splits
+            .into_iter()
+            .map(|(denom, split)| DenomSplit {
+                denom: denom.to_string(),
+                split,
+                min_distribution: None,
+            })
+            .collect(),
+        fallback_split: None,
+        fallback_min_distribution: None,
+        owner: Some(OWNER.to_string()),
+        members: vec![],
+        distribution_mode: None,
+        remainder_policy: None,
+        cw20_splits: vec![],
+        ibc_fee: None,
+        guardian: None,
+        keeper_tip: None,
+        cron_schedule: None,
+        swap_config: None,
+    }
+}
+
+struct Suite {
+    app: NeutronApp,
+    owner: Addr,
+    splitter: Addr,
+}
+
+impl Suite {
+    fn new(msg: InstantiateMsg, balances: Vec<Coin>) -> Suite {
+        let owner = Addr::unchecked(OWNER);
+        let mut app = neutron_app(NeutronMock::new(min_ibc_fee()));
+
+        let code_id = app.store_code(splitter_contract());
+        let splitter = app
+            .instantiate_contract(
+                code_id,
+                owner.clone(),
+                &msg,
+                &[],
+                "splitter",
+                Some(owner.to_string()),
+            )
+            .unwrap();
+
+        app.init_modules(|router, _, storage| {
+            router.bank.init_balance(storage, &splitter, balances)
+        })
+        .unwrap();
+
+        Suite {
+            app,
+            owner,
+            splitter,
+        }
+    }
+
+    fn execute(&mut self, msg: ExecuteMsg) -> AnyResult<AppResponse> {
+        self.app
+            .execute_contract(self.owner.clone(), self.splitter.clone(), &msg, &[])
+    }
+
+    fn cron_tick(&mut self) -> AnyResult<AppResponse> {
+        self.app
+            .wasm_sudo(self.splitter.clone(), &CronSudoMsg::CronTick {})
+    }
+
+    /// adds the coins to the splitter balance
+    fn mint(&mut self, amount: Vec<Coin>) {
+        self.app
+            .sudo(
+                BankSudo::Mint {
+                    to_address: self.splitter.to_string(),
+                    amount,
+                }
+                .into(),
+            )
+            .unwrap();
+    }
+
+    fn balance(&self, addr: &str, denom: &str) -> Uint128 {
+        self.app.wrap().query_balance(addr, denom).unwrap().amount
+    }
+
+    fn claimable(&self, addr: &str) -> Vec<Coin> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.splitter,
+                &QueryMsg::Claimable {
+                    addr: addr.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    fn ibc_transfers(&self) -> Vec<SentTransfer> {
+        ibc_transfers(&self.app)
+    }
+
+    /// acknowledges the transfer as received
+    fn ack_transfer(&mut self, transfer: &SentTransfer) {
+        let msg = SudoMsg::Response {
+            request: request_packet(transfer),
+            data: Binary::default(),
+        };
+        self.app.wasm_sudo(self.splitter.clone(), &msg).unwrap();
+    }
+
+    /// fails the transfer with an error ack, or with a timeout if no error
+    /// is given. the transfer module refunds the transferred funds.
+    fn fail_transfer(&mut self, transfer: &SentTransfer, error: Option<&str>) {
+        self.app
+            .send_tokens(
+                Addr::unchecked(IBC_ESCROW),
+                self.splitter.clone(),
+                &[transfer.token.clone()],
+            )
+            .unwrap();
+
+        let request = request_packet(transfer);
+        let msg = match error {
+            Some(details) => SudoMsg::Error {
+                request,
+                details: details.to_string(),
+            },
+            None => SudoMsg::Timeout { request },
+        };
+        self.app.wasm_sudo(self.splitter.clone(), &msg).unwrap();
+    }
+}
+
+fn request_packet(transfer: &SentTransfer) -> RequestPacket {
+    RequestPacket {
+        sequence: Some(transfer.sequence),
+        source_port: Some("transfer".to_string()),
+        source_channel: Some(transfer.channel.to_string()),
+        destination_port: Some("transfer".to_string()),
+        destination_channel: Some("channel-100".to_string()),
+        data: None,
+        timeout_height: None,
+        timeout_timestamp: None,
+    }
+}
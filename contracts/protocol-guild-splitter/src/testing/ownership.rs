@@ -0,0 +1,136 @@
+use anyhow::Result as AnyResult;
+use cosmwasm_std::Addr;
+use cw_multi_test::{AppResponse, Executor};
+use guild_interfaces::admin::OwnershipResponse;
+
+use crate::msg::{DenomSplit, ExecuteMsg, QueryMsg};
+
+use super::{instantiate_msg, split, Suite};
+
+fn ownership(suite: &Suite) -> OwnershipResponse {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::Ownership {})
+        .unwrap()
+}
+
+fn execute_as(suite: &mut Suite, sender: &str, msg: ExecuteMsg) -> AnyResult<AppResponse> {
+    suite
+        .app
+        .execute_contract(Addr::unchecked(sender), suite.splitter.clone(), &msg, &[])
+}
+
+fn update_splits() -> ExecuteMsg {
+    ExecuteMsg::UpdateSplits {
+        set: vec![DenomSplit {
+            denom: "uosmo".to_string(),
+            split: split(&[("alice", 100)]),
+            min_distribution: None,
+        }],
+        remove: vec![],
+    }
+}
+
+fn update_fallback() -> ExecuteMsg {
+    ExecuteMsg::UpdateFallback {
+        split: Some(split(&[("alice", 100)])),
+        min_distribution: None,
+    }
+}
+
+#[test]
+fn split_updates_are_owner_only() {
+    let msg = instantiate_msg(vec![("uatom", split(&[("alice", 100)]))]);
+    let mut suite = Suite::new(msg, vec![]);
+
+    let err = execute_as(&mut suite, "mallory", update_splits()).unwrap_err();
+    assert!(err.root_cause().to_string().contains("unauthorized caller"));
+    let err = execute_as(&mut suite, "mallory", update_fallback()).unwrap_err();
+    assert!(err.root_cause().to_string().contains("unauthorized caller"));
+
+    suite.execute(update_splits()).unwrap();
+    suite.execute(update_fallback()).unwrap();
+}
+
+#[test]
+fn ownership_moves_once_accepted() {
+    let msg = instantiate_msg(vec![("uatom", split(&[("alice", 100)]))]);
+    let mut suite = Suite::new(msg, vec![]);
+
+    let err = execute_as(
+        &mut suite,
+        "mallory",
+        ExecuteMsg::TransferOwnership {
+            new_owner: "mallory".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(err.root_cause().to_string().contains("unauthorized caller"));
+
+    let err = execute_as(&mut suite, "bob", ExecuteMsg::AcceptOwnership {}).unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .contains("no pending ownership transfer"));
+
+    suite
+        .execute(ExecuteMsg::TransferOwnership {
+            new_owner: "bob".to_string(),
+        })
+        .unwrap();
+    assert_eq!(
+        ownership(&suite),
+        OwnershipResponse {
+            owner: Some(suite.owner.clone()),
+            pending_owner: Some(Addr::unchecked("bob")),
+        }
+    );
+
+    // only the pending owner can accept
+    let err = execute_as(&mut suite, "mallory", ExecuteMsg::AcceptOwnership {}).unwrap_err();
+    assert!(err.root_cause().to_string().contains("unauthorized caller"));
+
+    execute_as(&mut suite, "bob", ExecuteMsg::AcceptOwnership {}).unwrap();
+    assert_eq!(
+        ownership(&suite),
+        OwnershipResponse {
+            owner: Some(Addr::unchecked("bob")),
+            pending_owner: None,
+        }
+    );
+
+    // the previous owner lost its rights
+    let err = suite.execute(update_splits()).unwrap_err();
+    assert!(err.root_cause().to_string().contains("unauthorized caller"));
+    execute_as(&mut suite, "bob", update_splits()).unwrap();
+}
+
+#[test]
+fn retransfer_replaces_the_pending_owner() {
+    let msg = instantiate_msg(vec![("uatom", split(&[("alice", 100)]))]);
+    let mut suite = Suite::new(msg, vec![]);
+
+    suite
+        .execute(ExecuteMsg::TransferOwnership {
+            new_owner: "bob".to_string(),
+        })
+        .unwrap();
+    suite
+        .execute(ExecuteMsg::TransferOwnership {
+            new_owner: "carol".to_string(),
+        })
+        .unwrap();
+
+    let err = execute_as(&mut suite, "bob", ExecuteMsg::AcceptOwnership {}).unwrap_err();
+    assert!(err.root_cause().to_string().contains("unauthorized caller"));
+
+    execute_as(&mut suite, "carol", ExecuteMsg::AcceptOwnership {}).unwrap();
+    assert_eq!(
+        ownership(&suite),
+        OwnershipResponse {
+            owner: Some(Addr::unchecked("carol")),
+            pending_owner: None,
+        }
+    );
+}
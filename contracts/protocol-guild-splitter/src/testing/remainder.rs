@@ -0,0 +1,35 @@
+use cosmwasm_std::{coin, coins, Coin, Uint128};
+
+use crate::msg::{ExecuteMsg, QueryMsg};
+
+use super::{instantiate_msg, split, Suite};
+
+fn carried_dust(suite: &Suite) -> Vec<Coin> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::CarriedDust {})
+        .unwrap()
+}
+
+#[test]
+fn dust_is_split_on_the_next_tick() {
+    let msg = instantiate_msg(vec![("uatom", split(&[("alice", 50), ("bob", 50)]))]);
+    let mut suite = Suite::new(msg, coins(101, "uatom"));
+
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(50));
+    assert_eq!(suite.balance("bob", "uatom"), Uint128::new(50));
+    assert_eq!(carried_dust(&suite), vec![coin(1, "uatom")]);
+    assert_eq!(
+        suite.balance(suite.splitter.as_str(), "uatom"),
+        Uint128::new(1)
+    );
+
+    // the carried dust adds up with new funds and nothing is left over
+    suite.mint(coins(1, "uatom"));
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(51));
+    assert_eq!(suite.balance("bob", "uatom"), Uint128::new(51));
+    assert!(carried_dust(&suite).is_empty());
+}
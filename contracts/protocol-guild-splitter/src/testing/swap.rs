@@ -0,0 +1,253 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128, Uint64};
+use cw_multi_test::Executor;
+
+use crate::msg::{
+    ExecuteMsg, FailedSwap, IbcDestination, IbcTransferInfo, QueryMsg, Receiver, SplitConfig,
+    SplitType, SwapConfig,
+};
+
+use super::{instantiate_msg, SentTransfer, Suite};
+
+const SWAP_CHANNEL_ID: &str = "channel-7";
+const SWAP_CONTRACT: &str = "osmo1swap";
+const PAYOUT_DENOM: &str = "uosmo";
+
+/// memo the crosschain swaps contract on osmosis expects through
+/// ibc-hooks, mirrored from the types of that contract
+#[cw_serde]
+struct VenueMemo {
+    wasm: VenueWasm,
+}
+
+#[cw_serde]
+struct VenueWasm {
+    contract: String,
+    msg: VenueMsg,
+}
+
+#[cw_serde]
+enum VenueMsg {
+    OsmosisSwap {
+        output_denom: String,
+        slippage: VenueSlippage,
+        receiver: String,
+        on_failed_delivery: VenueFailedDelivery,
+    },
+}
+
+#[cw_serde]
+enum VenueSlippage {
+    Twap {
+        window_seconds: Option<u64>,
+        slippage_percentage: Decimal,
+    },
+}
+
+#[cw_serde]
+enum VenueFailedDelivery {
+    LocalRecoveryAddr(String),
+}
+
+/// stands in for the swap venue on osmosis: only transfers to the swap
+/// contract whose memo it understands get swapped
+fn venue_swap(transfer: &SentTransfer) -> VenueMsg {
+    assert_eq!(transfer.channel, SWAP_CHANNEL_ID);
+    assert_eq!(transfer.receiver, SWAP_CONTRACT);
+    let memo: VenueMemo = serde_json_wasm::from_str(&transfer.memo).unwrap();
+    assert_eq!(memo.wasm.contract, SWAP_CONTRACT);
+    memo.wasm.msg
+}
+
+fn swap_config() -> SwapConfig {
+    SwapConfig {
+        channel_id: SWAP_CHANNEL_ID.to_string(),
+        swap_contract: SWAP_CONTRACT.to_string(),
+        max_slippage_percentage: Decimal::percent(500),
+        twap_window: Uint64::new(10),
+        recovery_addr: "osmo1recovery".to_string(),
+        timeout: Uint64::new(600),
+    }
+}
+
+/// splitter paying 60% of uatom to alice in uosmo on osmosis and 40%
+/// to bob on neutron. the first tick sends alice's split to the venue.
+fn swap_suite() -> (Suite, SentTransfer) {
+    let split = SplitType::Custom(SplitConfig {
+        receivers: vec![
+            Receiver {
+                addr: "alice".to_string(),
+                share: Decimal::percent(60),
+                ibc: Some(IbcDestination {
+                    channel_id: SWAP_CHANNEL_ID.to_string(),
+                    remote_addr: "osmo1alice".to_string(),
+                    timeout: Uint64::new(600),
+                }),
+                payout_denom: Some(PAYOUT_DENOM.to_string()),
+            },
+            Receiver {
+                addr: "bob".to_string(),
+                share: Decimal::percent(40),
+                ibc: None,
+                payout_denom: None,
+            },
+        ],
+    });
+    let mut msg = instantiate_msg(vec![("uatom", split)]);
+    msg.swap_config = Some(swap_config());
+
+    // the untrn balance covers the fees of the transfer to the venue
+    let mut suite = Suite::new(msg, vec![coin(1000, "uatom"), coin(20_000, "untrn")]);
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.balance("bob", "uatom"), Uint128::new(400));
+
+    let transfers = suite.ibc_transfers();
+    assert_eq!(transfers.len(), 1);
+    let transfer = transfers[0].clone();
+    assert_eq!(transfer.token, coin(600, "uatom"));
+
+    (suite, transfer)
+}
+
+fn failed_swaps(suite: &Suite) -> Vec<((String, u64), FailedSwap)> {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.splitter,
+            &QueryMsg::FailedSwaps {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn swap_slippage_is_bounded() {
+    let mut config = swap_config();
+
+    config.max_slippage_percentage = Decimal::zero();
+    assert_eq!(
+        config.validate().unwrap_err().to_string(),
+        "misconfigured swap venue"
+    );
+
+    config.max_slippage_percentage = Decimal::percent(5_000);
+    config.validate().unwrap();
+
+    config.max_slippage_percentage = Decimal::permille(50_001);
+    assert_eq!(
+        config.validate().unwrap_err().to_string(),
+        "misconfigured swap venue"
+    );
+}
+
+#[test]
+fn swap_memo_shape() {
+    let memo = swap_config()
+        .get_swap_memo(PAYOUT_DENOM, "osmo1alice")
+        .unwrap();
+    assert_eq!(
+        memo,
+        r#"{"wasm":{"contract":"osmo1swap","msg":{"osmosis_swap":{"output_denom":"uosmo","slippage":{"twap":{"slippage_percentage":"5","window_seconds":10}},"receiver":"osmo1alice","on_failed_delivery":{"local_recovery_addr":"osmo1recovery"}}}}}"#
+    );
+}
+
+#[test]
+fn swaps_through_venue() {
+    let (mut suite, transfer) = swap_suite();
+
+    assert_eq!(
+        venue_swap(&transfer),
+        VenueMsg::OsmosisSwap {
+            output_denom: PAYOUT_DENOM.to_string(),
+            slippage: VenueSlippage::Twap {
+                window_seconds: Some(10),
+                slippage_percentage: Decimal::percent(500),
+            },
+            receiver: "osmo1alice".to_string(),
+            on_failed_delivery: VenueFailedDelivery::LocalRecoveryAddr("osmo1recovery".to_string()),
+        }
+    );
+
+    suite.ack_transfer(&transfer);
+
+    let pending: Vec<((String, u64), IbcTransferInfo)> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.splitter, &QueryMsg::PendingIbcTransfers {})
+        .unwrap();
+    assert!(pending.is_empty());
+    assert!(failed_swaps(&suite).is_empty());
+    assert!(suite.claimable("alice").is_empty());
+}
+
+#[test]
+fn failed_swap_is_claimable() {
+    let (mut suite, transfer) = swap_suite();
+    venue_swap(&transfer);
+
+    suite.fail_transfer(&transfer, Some("slippage exceeded"));
+
+    let failed_at = suite.app.block_info().time;
+    assert_eq!(
+        failed_swaps(&suite),
+        vec![(
+            (SWAP_CHANNEL_ID.to_string(), transfer.sequence),
+            FailedSwap {
+                addr: "alice".to_string(),
+                coin: coin(600, "uatom"),
+                payout_denom: PAYOUT_DENOM.to_string(),
+                error: "slippage exceeded".to_string(),
+                failed_at,
+            }
+        )]
+    );
+    assert_eq!(suite.claimable("alice"), coins(600, "uatom"));
+
+    // credited funds are held back from the next tick and can be claimed
+    suite.execute(ExecuteMsg::Tick {}).unwrap();
+    assert_eq!(suite.ibc_transfers().len(), 1);
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            suite.splitter.clone(),
+            &ExecuteMsg::Claim { denoms: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.balance("alice", "uatom"), Uint128::new(600));
+    assert!(suite.claimable("alice").is_empty());
+}
+
+#[test]
+fn timed_out_swap_is_claimable() {
+    let (mut suite, transfer) = swap_suite();
+
+    suite.fail_transfer(&transfer, None);
+
+    let failed = failed_swaps(&suite);
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].1.error, "timeout");
+    assert_eq!(failed[0].1.coin, coin(600, "uatom"));
+    assert_eq!(suite.claimable("alice"), coins(600, "uatom"));
+}
+
+#[test]
+fn emergency_withdraw_keeps_failed_swaps() {
+    let (mut suite, transfer) = swap_suite();
+
+    suite.fail_transfer(&transfer, Some("slippage exceeded"));
+    suite.mint(coins(100, "uatom"));
+
+    suite
+        .execute(ExecuteMsg::EmergencyWithdraw {
+            recovery_address: "recovery".to_string(),
+        })
+        .unwrap();
+    assert_eq!(suite.balance("recovery", "uatom"), Uint128::new(100));
+    assert_eq!(suite.claimable("alice"), coins(600, "uatom"));
+    assert_eq!(failed_swaps(&suite).len(), 1);
+}
@@ -24,6 +24,9 @@ import (
 // provider_reward_denoms - the reward denominations allowed to be
 // sent to the consumer by the provider [^2].
 //
+// query_deposit - the deposit taken for every registered interchain
+// query, see `icqDeposit`.
+//
 // [^1]: https://docs.neutron.org/neutron/consumer-chain-launch#relevant-parameters
 // [^2]: https://github.com/cosmos/interchain-security/blob/54e9852d3c89a2513cd0170a56c6eec894fc878d/proto/interchain_security/ccv/consumer/v1/consumer.proto#L61-L66
 func setupNeutronGenesis(
@@ -53,6 +56,11 @@ func setupNeutronGenesis(
 			return nil, fmt.Errorf("failed to set allow_messages for interchainaccount host in genesis json: %w", err)
 		}
 
+		queryDeposit := []map[string]string{{"denom": chainConfig.Denom, "amount": fmt.Sprint(icqDeposit)}}
+		if err := dyno.Set(g, queryDeposit, "app_state", "interchainqueries", "params", "query_deposit"); err != nil {
+			return nil, fmt.Errorf("failed to set query_deposit for interchainqueries in genesis json: %w", err)
+		}
+
 		out, err := json.Marshal(g)
 
 		if err != nil {
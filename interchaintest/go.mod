@@ -4,6 +4,7 @@ go 1.20
 
 require (
 	github.com/cosmos/cosmos-sdk v0.45.16
+	github.com/docker/docker v20.10.19+incompatible
 	github.com/icza/dyno v0.0.0-20230330125955-09f820a8d9c0
 	github.com/strangelove-ventures/interchaintest/v4 v4.0.0-20230316161044-8d8c01f96b4a
 	github.com/stretchr/testify v1.8.4
@@ -59,7 +60,6 @@ require (
 	github.com/dgraph-io/ristretto v0.1.0 // indirect
 	github.com/dgryski/go-farm v0.0.0-20200201041132-a6ae2369ad13 // indirect
 	github.com/docker/distribution v2.8.1+incompatible // indirect
-	github.com/docker/docker v20.10.19+incompatible // indirect
 	github.com/docker/go-connections v0.4.0 // indirect
 	github.com/docker/go-units v0.5.0 // indirect
 	github.com/dustin/go-humanize v1.0.1-0.20200219035652-afde56e7acac // indirect
@@ -0,0 +1,117 @@
+package ibc_test
+
+import (
+	"context"
+	"fmt"
+	"strings"
+	"testing"
+
+	"github.com/docker/docker/api/types"
+	"github.com/docker/docker/api/types/container"
+	"github.com/docker/docker/api/types/mount"
+	"github.com/docker/docker/api/types/network"
+	"github.com/docker/docker/client"
+	"github.com/strangelove-ventures/interchaintest/v4/chain/cosmos"
+	"github.com/stretchr/testify/require"
+)
+
+// The neutron query relayer is not published as a prebuilt image. It has to be
+// built from https://github.com/neutron-org/neutron-query-relayer with
+// `make build-docker` before running the tests.
+const icqRelayerImage = "neutron-org/neutron-query-relayer:latest"
+
+// deposit the interchainqueries module takes for every registered query.
+// the forwarders register their balance query out of their own balance,
+// so they have to be funded with it.
+const icqDeposit = 1_000_000
+
+// Starts a neutron query relayer submitting the results of interchain queries
+// registered on `connectionId` with `targetChain`.
+//
+// The relayer signs with the key of `keyName`, so it is run against the
+// keyring of the neutron node that holds the test user keys. Its volume is
+// mounted into the relayer container.
+func startIcqRelayer(
+	t *testing.T,
+	ctx context.Context,
+	dockerClient *client.Client,
+	networkId string,
+	neutron *cosmos.CosmosChain,
+	targetChain *cosmos.CosmosChain,
+	connectionId string,
+	keyName string,
+) {
+	// test user keys live on the node interchaintest runs its commands on
+	keyringNode := neutron.Validators[0]
+	if len(neutron.FullNodes) > 0 {
+		keyringNode = neutron.FullNodes[0]
+	}
+
+	env := []string{
+		"RELAYER_NEUTRON_CHAIN_RPC_ADDR=" + neutron.GetRPCAddress(),
+		"RELAYER_NEUTRON_CHAIN_REST_ADDR=" + neutron.GetAPIAddress(),
+		"RELAYER_NEUTRON_CHAIN_HOME_DIR=" + neutron.HomeDir(),
+		"RELAYER_NEUTRON_CHAIN_SIGN_KEY_NAME=" + keyName,
+		"RELAYER_NEUTRON_CHAIN_KEYRING_BACKEND=test",
+		"RELAYER_NEUTRON_CHAIN_CONNECTION_ID=" + connectionId,
+		"RELAYER_NEUTRON_CHAIN_DENOM=" + nativeNtrnDenom,
+		"RELAYER_NEUTRON_CHAIN_GAS_PRICES=0.5" + nativeNtrnDenom,
+		"RELAYER_NEUTRON_CHAIN_MAX_GAS_PRICE=1000",
+		"RELAYER_NEUTRON_CHAIN_GAS_PRICE_MULTIPLIER=1.1",
+		"RELAYER_NEUTRON_CHAIN_GAS_LIMIT=10000000",
+		"RELAYER_NEUTRON_CHAIN_GAS_ADJUSTMENT=1.5",
+		"RELAYER_NEUTRON_CHAIN_OUTPUT_FORMAT=json",
+		"RELAYER_NEUTRON_CHAIN_SIGN_MODE_STR=direct",
+		"RELAYER_TARGET_CHAIN_RPC_ADDR=" + targetChain.GetRPCAddress(),
+		"RELAYER_TARGET_CHAIN_ACCOUNT_PREFIX=" + targetChain.Config().Bech32Prefix,
+		"RELAYER_TARGET_CHAIN_VALIDATOR_ACCOUNT_PREFIX=" + targetChain.Config().Bech32Prefix + "valoper",
+		"RELAYER_TARGET_CHAIN_TIMEOUT=10s",
+		"RELAYER_TARGET_CHAIN_OUTPUT_FORMAT=json",
+		"RELAYER_REGISTRY_ADDRESSES=",
+		"RELAYER_ALLOW_TX_QUERIES=false",
+		"RELAYER_ALLOW_KV_CALLBACKS=true",
+		"RELAYER_STORAGE_PATH=/tmp/storage/leveldb",
+		"RELAYER_QUERIES_TASK_QUEUE_CAPACITY=10000",
+		"RELAYER_CHECK_SUBMITTED_TX_STATUS_DELAY=10s",
+		"RELAYER_INITIAL_TX_SEARCH_OFFSET=0",
+		"RELAYER_WEBSERVER_PORT=127.0.0.1:10001",
+		"LOGGER_LEVEL=info",
+	}
+
+	resp, err := dockerClient.ContainerCreate(
+		ctx,
+		&container.Config{
+			Image: icqRelayerImage,
+			Env:   env,
+			// the node volume is owned by the heighliner user
+			User: "1025:1025",
+		},
+		&container.HostConfig{
+			Mounts: []mount.Mount{
+				{
+					Type:   mount.TypeVolume,
+					Source: keyringNode.VolumeName,
+					Target: neutron.HomeDir(),
+				},
+			},
+		},
+		&network.NetworkingConfig{
+			EndpointsConfig: map[string]*network.EndpointSettings{
+				networkId: {},
+			},
+		},
+		nil,
+		fmt.Sprintf("icq-relayer-%s-%s", targetChain.Config().ChainID, strings.ReplaceAll(t.Name(), "/", "-")),
+	)
+	require.NoError(t, err, "failed to create icq relayer container")
+
+	t.Cleanup(func() {
+		err := dockerClient.ContainerRemove(context.Background(), resp.ID, types.ContainerRemoveOptions{Force: true})
+		if err != nil {
+			t.Logf("failed to remove icq relayer container: %s", err)
+		}
+	})
+
+	err = dockerClient.ContainerStart(ctx, resp.ID, types.ContainerStartOptions{})
+	require.NoError(t, err, "failed to start icq relayer")
+}
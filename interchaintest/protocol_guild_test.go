@@ -270,6 +270,12 @@ func TestProtocolGuild(t *testing.T) {
 
 	gaiaUser, neutronUser, osmoUser := users[0], users[1], users[2]
 
+	// the forwarders only forward the ICA balances reported by their
+	// interchain queries, so every remote chain needs a query relayer
+	icqRelayerUsers := ibctest.GetAndFundTestUsers(t, ctx, "icq", int64(10_000_000), neutron, neutron)
+	startIcqRelayer(t, ctx, client, network, cosmosNeutron, cosmosOsmosis, neutronOsmosisIBCConnId, icqRelayerUsers[0].KeyName)
+	startIcqRelayer(t, ctx, client, network, cosmosNeutron, cosmosAtom, neutronAtomIBCConnId, icqRelayerUsers[1].KeyName)
+
 	err = testutil.WaitForBlocks(ctx, 10, atom, neutron, osmosis)
 	require.NoError(t, err, "failed to wait for blocks")
 
@@ -350,15 +356,15 @@ func TestProtocolGuild(t *testing.T) {
 								Receivers: []Receiver{
 									{
 										Address: cosmwasmMaintainer.Bech32Address(cosmosNeutron.Config().Bech32Prefix),
-										Share:   "20",
+										Share:   "0.2",
 									},
 									{
 										Address: sdkDevTeam.Bech32Address(cosmosNeutron.Config().Bech32Prefix),
-										Share:   "70",
+										Share:   "0.7",
 									},
 									{
 										Address: bugBounty.Bech32Address(cosmosNeutron.Config().Bech32Prefix),
-										Share:   "10",
+										Share:   "0.1",
 									},
 								},
 							},
@@ -371,15 +377,15 @@ func TestProtocolGuild(t *testing.T) {
 								Receivers: []Receiver{
 									{
 										Address: sdkDevTeam.Bech32Address(cosmosNeutron.Config().Bech32Prefix),
-										Share:   "50",
+										Share:   "0.5",
 									},
 									{
 										Address: docsUpkeep.Bech32Address(cosmosNeutron.Config().Bech32Prefix),
-										Share:   "40",
+										Share:   "0.4",
 									},
 									{
 										Address: discordOpsTeam.Bech32Address(cosmosNeutron.Config().Bech32Prefix),
-										Share:   "10",
+										Share:   "0.1",
 									},
 								},
 							},
@@ -387,6 +393,8 @@ func TestProtocolGuild(t *testing.T) {
 					},
 				},
 				FallbackSplit: nil,
+				Members:       []Member{},
+				Cw20Splits:    []Cw20Split{},
 			}
 
 			str, err := json.Marshal(protocolGuildInstantiateMsg)
@@ -399,16 +407,25 @@ func TestProtocolGuild(t *testing.T) {
 
 			println("splitter  address: ", splitterAddress)
 
+			var splitResponse DenomSplitResponse
+			err = cosmosNeutron.QueryContract(ctx, splitterAddress, DenomSplitQuery{
+				DenomSplit: DenomSplitQueryMsg{Denom: neutronOsmoIbcDenom},
+			}, &splitResponse)
+			require.NoError(t, err, "failed to query osmo split")
+			require.Equal(t, protocolGuildInstantiateMsg.Splits[0].Type, splitResponse.Data)
+
 			icaTimeout := "100"
 			ibcTransferTimeout := "100"
+			icqUpdatePeriod := "5"
 
 			osmoForwarderInstantiateMsg := IbcForwarderInstantiateMsg{
 				NextContract:            splitterAddress,
 				RemoteChainConnectionId: neutronOsmosisIBCConnId,
 				RemoteChainChannelId:    testCtx.OsmoTransferChannelIds[cosmosNeutron.Config().Name],
-				Denom:                   nativeOsmoDenom,
+				Denoms:                  []string{nativeOsmoDenom},
 				IbcTransferTimeout:      ibcTransferTimeout,
 				IcaTimeout:              icaTimeout,
+				IcqUpdatePeriod:         icqUpdatePeriod,
 			}
 
 			str, err = json.Marshal(osmoForwarderInstantiateMsg)
@@ -423,9 +440,10 @@ func TestProtocolGuild(t *testing.T) {
 				NextContract:            splitterAddress,
 				RemoteChainConnectionId: neutronAtomIBCConnId,
 				RemoteChainChannelId:    testCtx.GaiaTransferChannelIds[cosmosNeutron.Config().Name],
-				Denom:                   nativeAtomDenom,
+				Denoms:                  []string{nativeAtomDenom},
 				IbcTransferTimeout:      ibcTransferTimeout,
 				IcaTimeout:              icaTimeout,
+				IcqUpdatePeriod:         icqUpdatePeriod,
 			}
 
 			str, err = json.Marshal(gaiaForwarderInstantiateMsg)
@@ -441,17 +459,19 @@ func TestProtocolGuild(t *testing.T) {
 		})
 
 		t.Run("fund contracts with neutron", func(t *testing.T) {
+			// forwarders pay the deposit of their balance query on top of
+			// the fees of their ICA txs
 			require.NoError(t,
 				neutron.SendFunds(ctx, neutronUser.KeyName, ibc.WalletAmount{
 					Address: gaiaForwarderAddress,
-					Amount:  500000,
+					Amount:  500000 + icqDeposit,
 					Denom:   nativeNtrnDenom,
 				}),
 				"failed to send funds from neutron user to gaiaForwarderAddress")
 			require.NoError(t,
 				neutron.SendFunds(ctx, neutronUser.KeyName, ibc.WalletAmount{
 					Address: osmoForwarderAddress,
-					Amount:  500000,
+					Amount:  500000 + icqDeposit,
 					Denom:   nativeNtrnDenom,
 				}),
 				"failed to send funds from neutron user to osmoForwarderAddress")
@@ -465,25 +485,26 @@ func TestProtocolGuild(t *testing.T) {
 
 			bal, err := neutron.GetBalance(ctx, gaiaForwarderAddress, nativeNtrnDenom)
 			require.NoError(t, err)
-			require.Equal(t, int64(500000), bal)
+			require.Equal(t, int64(500000+icqDeposit), bal)
 			bal, err = neutron.GetBalance(ctx, osmoForwarderAddress, nativeNtrnDenom)
 			require.NoError(t, err)
-			require.Equal(t, int64(500000), bal)
+			require.Equal(t, int64(500000+icqDeposit), bal)
 			bal, err = neutron.GetBalance(ctx, splitterAddress, nativeNtrnDenom)
 			require.NoError(t, err)
 			require.Equal(t, int64(500000), bal)
 		})
 
-		queryBalances := func(addr string, label string) {
+		// asserts the (osmo, atom) balances of the address on neutron
+		requireBalances := func(t *testing.T, addr string, osmoBal int64, atomBal int64) {
 			neutronOsmoBal, err := cosmosNeutron.GetBalance(ctx, addr, neutronOsmoIbcDenom)
 			require.NoError(t, err)
+			require.Equal(t, osmoBal, neutronOsmoBal, "unexpected osmo balance of %s", addr)
 			neutronAtomBal, err := cosmosNeutron.GetBalance(ctx, addr, neutronAtomIbcDenom)
 			require.NoError(t, err)
-
-			println(label, " [neutronOsmoBal: ", neutronOsmoBal, ", neutronAtomBal: ", neutronAtomBal, "]")
+			require.Equal(t, atomBal, neutronAtomBal, "unexpected atom balance of %s", addr)
 		}
 
-		tickContract := func(addr string, label string) {
+		tryTickContract := func(addr string, label string) error {
 			println("ticking ", label)
 			cmd := []string{"neutrond", "tx", "wasm", "execute", addr,
 				`{"tick":{}}`,
@@ -501,10 +522,30 @@ func TestProtocolGuild(t *testing.T) {
 			}
 
 			_, _, err := cosmosNeutron.Exec(ctx, cmd, nil)
-			require.NoError(t, err)
 
-			err = testutil.WaitForBlocks(ctx, 5, atom, neutron, osmosis)
-			require.NoError(t, err, "failed to wait for blocks")
+			require.NoError(t, testutil.WaitForBlocks(ctx, 5, atom, neutron, osmosis), "failed to wait for blocks")
+			return err
+		}
+
+		tickContract := func(addr string, label string) {
+			require.NoError(t, tryTickContract(addr, label))
+		}
+
+		// ticks the forwarder until the splitter holds the expected balance.
+		// ticks are rejected until a fresh balance query result arrives, so
+		// their errors are expected.
+		tickForwarderUntil := func(t *testing.T, addr string, label string, denom string, splitterBal int64) {
+			for i := 0; i < 20; i++ {
+				if err := tryTickContract(addr, label); err != nil {
+					println(label, " tick rejected: ", err.Error())
+				}
+				bal, err := cosmosNeutron.GetBalance(ctx, splitterAddress, denom)
+				require.NoError(t, err)
+				if bal == splitterBal {
+					return
+				}
+			}
+			require.Fail(t, "forwarder did not forward its ICA balance", label)
 		}
 
 		t.Run("create ICAs for forwarders", func(t *testing.T) {
@@ -562,37 +603,61 @@ func TestProtocolGuild(t *testing.T) {
 			require.Equal(t, int64(500000), bal)
 		})
 
-		queryAllBalances := func() {
-			queryBalances(splitterAddress, "splitter")
-			queryBalances(sdkDevTeam.Bech32Address(cosmosNeutron.Config().Bech32Prefix), "sdk_dev_team")
-			queryBalances(cosmwasmMaintainer.Bech32Address(cosmosNeutron.Config().Bech32Prefix), "cosmwasm_maintainer")
-			queryBalances(discordOpsTeam.Bech32Address(cosmosNeutron.Config().Bech32Prefix), "discord_ops_team")
-			queryBalances(bugBounty.Bech32Address(cosmosNeutron.Config().Bech32Prefix), "bug_bounty")
-			queryBalances(docsUpkeep.Bech32Address(cosmosNeutron.Config().Bech32Prefix), "docs_upkeep")
-		}
+		sdkDevTeamAddress := sdkDevTeam.Bech32Address(cosmosNeutron.Config().Bech32Prefix)
+		cosmwasmMaintainerAddress := cosmwasmMaintainer.Bech32Address(cosmosNeutron.Config().Bech32Prefix)
+		discordOpsTeamAddress := discordOpsTeam.Bech32Address(cosmosNeutron.Config().Bech32Prefix)
+		bugBountyAddress := bugBounty.Bech32Address(cosmosNeutron.Config().Bech32Prefix)
+		docsUpkeepAddress := docsUpkeep.Bech32Address(cosmosNeutron.Config().Bech32Prefix)
 
 		t.Run("tick forwarders to forward funds from ica to splitter", func(t *testing.T) {
-			println("\ninitial balances: ")
-			queryAllBalances()
+			requireBalances(t, splitterAddress, 0, 0)
 
-			println("\ntick forwarders 10x to imitate some continuous stream of funds...")
-			for i := 0; i < 4; i++ {
-				tickContract(osmoForwarderAddress, "osmo forwarder")
-				tickContract(gaiaForwarderAddress, "gaia forwarder")
-			}
+			// each forwarder sends its whole ICA balance once the
+			// balance query reports it
+			tickForwarderUntil(t, osmoForwarderAddress, "osmo forwarder", neutronOsmoIbcDenom, 500000)
+			tickForwarderUntil(t, gaiaForwarderAddress, "gaia forwarder", neutronAtomIbcDenom, 500000)
+			requireBalances(t, splitterAddress, 500000, 500000)
+
+			bal, err := cosmosOsmosis.GetBalance(ctx, osmoForwarderICA, cosmosOsmosis.Config().Denom)
+			require.NoError(t, err)
+			require.Equal(t, int64(0), bal)
+			bal, err = cosmosAtom.GetBalance(ctx, gaiaForwarderICA, cosmosAtom.Config().Denom)
+			require.NoError(t, err)
+			require.Equal(t, int64(0), bal)
+		})
 
-			queryAllBalances()
-			println("\ndistribute the splits...")
+		t.Run("tick splitter to distribute the splits", func(t *testing.T) {
 			tickContract(splitterAddress, "splitter")
 
-			queryAllBalances()
+			requireBalances(t, splitterAddress, 0, 0)
+			requireBalances(t, cosmwasmMaintainerAddress, 100000, 0)
+			requireBalances(t, sdkDevTeamAddress, 350000, 250000)
+			requireBalances(t, bugBountyAddress, 50000, 0)
+			requireBalances(t, docsUpkeepAddress, 0, 200000)
+			requireBalances(t, discordOpsTeamAddress, 0, 50000)
+		})
 
-			println("\ne.g. one chain stops streaming, other continues..")
-			for i := 0; i < 4; i++ {
-				tickContract(gaiaForwarderAddress, "gaia forwarder")
-				tickContract(splitterAddress, "splitter\n")
-				queryAllBalances()
-			}
+		t.Run("forward and distribute funds of a single chain", func(t *testing.T) {
+			// e.g. one chain stops streaming, the other continues
+			require.NoError(t,
+				cosmosAtom.SendFunds(ctx, gaiaUser.KeyName, ibc.WalletAmount{
+					Address: gaiaForwarderICA,
+					Amount:  100000,
+					Denom:   cosmosAtom.Config().Denom,
+				}),
+				"failed to send funds from gaia user to gaiaForwarderICA")
+
+			tickForwarderUntil(t, gaiaForwarderAddress, "gaia forwarder", neutronAtomIbcDenom, 100000)
+			requireBalances(t, splitterAddress, 0, 100000)
+
+			tickContract(splitterAddress, "splitter")
+
+			requireBalances(t, splitterAddress, 0, 0)
+			requireBalances(t, cosmwasmMaintainerAddress, 100000, 0)
+			requireBalances(t, sdkDevTeamAddress, 350000, 300000)
+			requireBalances(t, bugBountyAddress, 50000, 0)
+			requireBalances(t, docsUpkeepAddress, 0, 240000)
+			requireBalances(t, discordOpsTeamAddress, 0, 60000)
 		})
 	})
 }
@@ -3,15 +3,18 @@ package ibc_test
 type ProtocolGuildSplitterInstantiateMsg struct {
 	Splits        []DenomSplit `json:"splits"`
 	FallbackSplit *SplitType   `json:"fallback_split,omitempty"`
+	Members       []Member     `json:"members"`
+	Cw20Splits    []Cw20Split  `json:"cw20_splits"`
 }
 
 type IbcForwarderInstantiateMsg struct {
-	NextContract            string `json:"next_contract"`
-	RemoteChainConnectionId string `json:"remote_chain_connection_id"`
-	RemoteChainChannelId    string `json:"remote_chain_channel_id"`
-	Denom                   string `json:"denom"`
-	IbcTransferTimeout      string `json:"ibc_transfer_timeout"`
-	IcaTimeout              string `json:"ica_timeout"`
+	NextContract            string   `json:"next_contract"`
+	RemoteChainConnectionId string   `json:"remote_chain_connection_id"`
+	RemoteChainChannelId    string   `json:"remote_chain_channel_id"`
+	Denoms                  []string `json:"denoms"`
+	IbcTransferTimeout      string   `json:"ibc_transfer_timeout"`
+	IcaTimeout              string   `json:"ica_timeout"`
+	IcqUpdatePeriod         string   `json:"icq_update_period"`
 }
 
 type Receiver struct {
@@ -19,6 +22,13 @@ type Receiver struct {
 	Share   string `json:"share"`
 }
 
+type Member struct {
+	Address string `json:"addr"`
+	Start   string `json:"start"`
+	Weight  string `json:"weight"`
+	Active  bool   `json:"active"`
+}
+
 type SplitConfig struct {
 	Receivers []Receiver `json:"receivers"`
 }
@@ -32,6 +42,11 @@ type DenomSplit struct {
 	Type  SplitType `json:"split"`
 }
 
+type Cw20Split struct {
+	Token string    `json:"token"`
+	Type  SplitType `json:"split"`
+}
+
 // queries
 type DepositAddress struct{}
 type DepositAddressQuery struct {
@@ -41,3 +56,16 @@ type DepositAddressQuery struct {
 type QueryResponse struct {
 	Data string `json:"data"`
 }
+
+type DenomSplitQuery struct {
+	DenomSplit DenomSplitQueryMsg `json:"denom_split"`
+}
+
+type DenomSplitQueryMsg struct {
+	Denom string `json:"denom"`
+}
+
+// `denom_split` returns a split type, which used to be a plain split config
+type DenomSplitResponse struct {
+	Data SplitType `json:"data"`
+}
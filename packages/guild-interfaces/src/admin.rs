@@ -0,0 +1,240 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, CustomQuery, Deps, DepsMut, MessageInfo, Response, StdError, StdResult, Storage,
+};
+use cw2::{get_contract_version, ContractVersion};
+use cw_storage_plus::Item;
+use neutron_sdk::NeutronError;
+use thiserror::Error;
+
+/// address allowed to configure the contract
+pub const OWNER: Item<Addr> = Item::new("owner");
+
+/// proposed owner that has yet to accept the ownership
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
+
+/// address allowed to pause the contract besides the owner
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+
+/// whether the contract is paused
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+#[derive(Error, Debug)]
+pub enum AdminError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("unauthorized caller")]
+    Unauthorized {},
+
+    #[error("no pending ownership transfer")]
+    NoPendingOwner {},
+
+    #[error("contract is paused")]
+    Paused {},
+
+    #[error("cannot migrate from {contract} {version}")]
+    InvalidMigration { contract: String, version: String },
+}
+
+impl From<AdminError> for NeutronError {
+    fn from(err: AdminError) -> Self {
+        match err {
+            AdminError::Std(e) => NeutronError::Std(e),
+            e => NeutronError::Std(StdError::generic_err(e.to_string())),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct OwnershipResponse {
+    pub owner: Option<Addr>,
+    /// owner proposed via `TransferOwnership` that has not accepted yet
+    pub pending_owner: Option<Addr>,
+}
+
+/// errors unless the sender is the current owner
+pub fn assert_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), AdminError> {
+    match OWNER.may_load(storage)? {
+        Some(owner) if owner == *sender => Ok(()),
+        _ => Err(AdminError::Unauthorized {}),
+    }
+}
+
+/// errors unless the sender is the current owner or the guardian
+pub fn assert_owner_or_guardian(storage: &dyn Storage, sender: &Addr) -> Result<(), AdminError> {
+    if GUARDIAN.may_load(storage)?.as_ref() == Some(sender) {
+        return Ok(());
+    }
+    assert_owner(storage, sender)
+}
+
+/// errors if the contract is paused
+pub fn assert_not_paused(storage: &dyn Storage) -> Result<(), AdminError> {
+    if is_paused(storage)? {
+        return Err(AdminError::Paused {});
+    }
+    Ok(())
+}
+
+pub fn is_paused(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(PAUSED.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn try_set_paused<Q: CustomQuery, T>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response<T>, AdminError> {
+    assert_owner_or_guardian(deps.storage, &info.sender)?;
+
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_set_paused")
+        .add_attribute("paused", paused.to_string()))
+}
+
+pub fn try_update_guardian<Q: CustomQuery, T>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    guardian: Option<String>,
+) -> Result<Response<T>, AdminError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    match guardian {
+        Some(guardian) => GUARDIAN.save(deps.storage, &deps.api.addr_validate(&guardian)?)?,
+        None => GUARDIAN.remove(deps.storage),
+    }
+
+    Ok(Response::default().add_attribute("method", "try_update_guardian"))
+}
+
+pub fn try_transfer_ownership<Q: CustomQuery, T>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+    new_owner: String,
+) -> Result<Response<T>, AdminError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    // ownership only changes once the new owner accepts it
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    PENDING_OWNER.save(deps.storage, &new_owner)?;
+
+    Ok(Response::default()
+        .add_attribute("method", "try_transfer_ownership")
+        .add_attribute("pending_owner", new_owner))
+}
+
+pub fn try_accept_ownership<Q: CustomQuery, T>(
+    deps: DepsMut<Q>,
+    info: MessageInfo,
+) -> Result<Response<T>, AdminError> {
+    let Some(pending_owner) = PENDING_OWNER.may_load(deps.storage)? else {
+        return Err(AdminError::NoPendingOwner {});
+    };
+    if pending_owner != info.sender {
+        return Err(AdminError::Unauthorized {});
+    }
+
+    OWNER.save(deps.storage, &pending_owner)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::default()
+        .add_attribute("method", "try_accept_ownership")
+        .add_attribute("owner", pending_owner))
+}
+
+pub fn query_ownership<Q: CustomQuery>(deps: Deps<Q>) -> StdResult<OwnershipResponse> {
+    Ok(OwnershipResponse {
+        owner: OWNER.may_load(deps.storage)?,
+        pending_owner: PENDING_OWNER.may_load(deps.storage)?,
+    })
+}
+
+/// returns the stored contract version along with its parsed semver.
+/// contracts only migrate from the same contract, and never to an
+/// older version.
+pub fn assert_migration(
+    storage: &dyn Storage,
+    contract_name: &str,
+    contract_version: &str,
+) -> Result<(ContractVersion, semver::Version), AdminError> {
+    let stored = get_contract_version(storage)?;
+    let stored_version = parse_version(&stored.version)?;
+    if stored.contract != contract_name || stored_version > parse_version(contract_version)? {
+        return Err(AdminError::InvalidMigration {
+            contract: stored.contract,
+            version: stored.version,
+        });
+    }
+    Ok((stored, stored_version))
+}
+
+pub fn parse_version(version: &str) -> StdResult<semver::Version> {
+    version
+        .parse()
+        .map_err(|e| StdError::generic_err(format!("invalid version {version}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+    use cw2::set_contract_version;
+
+    use super::*;
+
+    const CONTRACT_NAME: &str = "crates.io:ibc-forwarder";
+
+    fn storage_with_version(contract: &str, version: &str) -> MockStorage {
+        let mut storage = MockStorage::new();
+        set_contract_version(&mut storage, contract, version).unwrap();
+        storage
+    }
+
+    #[test]
+    fn migrates_to_the_same_or_a_newer_version() {
+        let storage = storage_with_version(CONTRACT_NAME, "0.1.0");
+
+        let (stored, stored_version) = assert_migration(&storage, CONTRACT_NAME, "0.1.0").unwrap();
+        assert_eq!(stored.contract, CONTRACT_NAME);
+        assert_eq!(stored_version, semver::Version::new(0, 1, 0));
+
+        let (stored, _) = assert_migration(&storage, CONTRACT_NAME, "0.2.0").unwrap();
+        assert_eq!(stored.version, "0.1.0");
+    }
+
+    #[test]
+    fn rejects_older_versions() {
+        let storage = storage_with_version(CONTRACT_NAME, "0.2.0");
+
+        let err = assert_migration(&storage, CONTRACT_NAME, "0.1.9").unwrap_err();
+        assert!(matches!(
+            err,
+            AdminError::InvalidMigration { contract, version }
+                if contract == CONTRACT_NAME && version == "0.2.0"
+        ));
+    }
+
+    #[test]
+    fn rejects_other_contracts() {
+        let storage = storage_with_version("crates.io:protocol-guild-splitter", "0.1.0");
+
+        let err = assert_migration(&storage, CONTRACT_NAME, "0.2.0").unwrap_err();
+        assert!(matches!(err, AdminError::InvalidMigration { .. }));
+    }
+
+    #[test]
+    fn rejects_missing_or_invalid_versions() {
+        let err = assert_migration(&MockStorage::new(), CONTRACT_NAME, "0.1.0").unwrap_err();
+        assert!(matches!(err, AdminError::Std(_)));
+
+        let storage = storage_with_version(CONTRACT_NAME, "not-semver");
+        let err = assert_migration(&storage, CONTRACT_NAME, "0.1.0").unwrap_err();
+        assert!(err.to_string().contains("invalid version not-semver"));
+
+        let storage = storage_with_version(CONTRACT_NAME, "0.1.0");
+        let err = assert_migration(&storage, CONTRACT_NAME, "latest").unwrap_err();
+        assert!(err.to_string().contains("invalid version latest"));
+    }
+}
@@ -0,0 +1,87 @@
+use cosmwasm_schema::cw_serde;
+use neutron_sdk::sudo::msg::SudoMsg;
+use serde::Deserialize;
+
+/// sudo messages of contracts ticked by the neutron cron module. cron
+/// ticks are matched first, anything else is a neutron IBC callback.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum ContractSudoMsg {
+    Cron(CronSudoMsg),
+    Neutron(SudoMsg),
+}
+
+#[cw_serde]
+pub enum CronSudoMsg {
+    /// ticks the contract the same way as `Tick`, without tipping anyone
+    CronTick {},
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{from_binary, from_slice, to_binary, Binary};
+    use neutron_sdk::sudo::msg::RequestPacket;
+
+    use super::*;
+
+    fn request() -> RequestPacket {
+        RequestPacket {
+            sequence: Some(1),
+            source_port: Some("icacontroller-contract.ica".to_string()),
+            source_channel: Some("channel-10".to_string()),
+            destination_port: Some("icahost".to_string()),
+            destination_channel: Some("channel-100".to_string()),
+            data: None,
+            timeout_height: None,
+            timeout_timestamp: None,
+        }
+    }
+
+    #[test]
+    fn cron_ticks_are_matched_first() {
+        let msg: ContractSudoMsg =
+            from_binary(&to_binary(&CronSudoMsg::CronTick {}).unwrap()).unwrap();
+        assert!(matches!(
+            msg,
+            ContractSudoMsg::Cron(CronSudoMsg::CronTick {})
+        ));
+    }
+
+    #[test]
+    fn neutron_callbacks_are_not_cron_ticks() {
+        let callbacks = vec![
+            SudoMsg::Response {
+                request: request(),
+                data: Binary::from(b"ack".as_slice()),
+            },
+            SudoMsg::Error {
+                request: request(),
+                details: "failed".to_string(),
+            },
+            SudoMsg::Timeout { request: request() },
+            SudoMsg::OpenAck {
+                port_id: "icacontroller-contract.ica".to_string(),
+                channel_id: "channel-10".to_string(),
+                counterparty_channel_id: "channel-100".to_string(),
+                counterparty_version: "{}".to_string(),
+            },
+            SudoMsg::KVQueryResult { query_id: 1 },
+        ];
+
+        for callback in callbacks {
+            let msg: ContractSudoMsg = from_binary(&to_binary(&callback).unwrap()).unwrap();
+            match msg {
+                ContractSudoMsg::Neutron(msg) => {
+                    assert_eq!(to_binary(&msg).unwrap(), to_binary(&callback).unwrap())
+                }
+                ContractSudoMsg::Cron(_) => panic!("{callback:?} was routed as a cron tick"),
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_sudo_msgs() {
+        from_slice::<ContractSudoMsg>(br#"{"cron_tick":{"extra":1}}"#).unwrap_err();
+        from_slice::<ContractSudoMsg>(br#"{"unknown":{}}"#).unwrap_err();
+    }
+}
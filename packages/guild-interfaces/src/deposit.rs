@@ -0,0 +1,38 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CustomQuery, QuerierWrapper, StdResult};
+
+/// query every contract receiving funds from a forwarder has to answer.
+/// contracts add the `DepositAddress {}` variant to their own `QueryMsg`.
+#[derive(QueryResponses)]
+#[cw_serde]
+pub enum DepositQueryMsg {
+    #[returns(DepositAddressResponse)]
+    DepositAddress {},
+}
+
+/// address funds should be sent to, or `None` if the contract is not
+/// ready to receive funds yet
+pub type DepositAddressResponse = Option<String>;
+
+/// whether a contract is ready to receive funds
+#[cw_serde]
+pub enum DepositReady {
+    Ready { address: String },
+    NotReady,
+}
+
+impl DepositReady {
+    /// queries the deposit address of the contract
+    pub fn check<Q: CustomQuery>(
+        querier: &QuerierWrapper<Q>,
+        contract: impl Into<String>,
+    ) -> StdResult<DepositReady> {
+        let response: DepositAddressResponse =
+            querier.query_wasm_smart(contract, &DepositQueryMsg::DepositAddress {})?;
+
+        Ok(match response {
+            Some(address) => DepositReady::Ready { address },
+            None => DepositReady::NotReady,
+        })
+    }
+}
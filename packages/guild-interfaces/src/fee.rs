@@ -0,0 +1,164 @@
+use cosmwasm_std::{Coin, Deps, StdError, StdResult};
+use neutron_sdk::{
+    bindings::{
+        msg::IbcFee,
+        query::{MinIbcFeeResponse, NeutronQuery},
+    },
+    NeutronError,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IbcFeeError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("IBC fee must have an empty recv_fee and cover the minimum ack and timeout fees")]
+    InvalidIbcFee {},
+}
+
+impl From<IbcFeeError> for NeutronError {
+    fn from(err: IbcFeeError) -> Self {
+        match err {
+            IbcFeeError::Std(e) => NeutronError::Std(e),
+            e => NeutronError::Std(StdError::generic_err(e.to_string())),
+        }
+    }
+}
+
+pub fn query_min_ibc_fee(deps: Deps<NeutronQuery>) -> StdResult<IbcFee> {
+    let response: MinIbcFeeResponse = deps.querier.query(&NeutronQuery::MinIbcFee {}.into())?;
+    Ok(response.min_fee)
+}
+
+/// the feerefunder module rejects fees with a recv fee or with ack and
+/// timeout fees below its minimum
+pub fn validate_ibc_fee(deps: Deps<NeutronQuery>, ibc_fee: IbcFee) -> Result<IbcFee, IbcFeeError> {
+    let min_fee = query_min_ibc_fee(deps)?;
+
+    if !ibc_fee.recv_fee.is_empty()
+        || !covers_fee(&ibc_fee.ack_fee, &min_fee.ack_fee)
+        || !covers_fee(&ibc_fee.timeout_fee, &min_fee.timeout_fee)
+    {
+        return Err(IbcFeeError::InvalidIbcFee {});
+    }
+
+    Ok(ibc_fee)
+}
+
+/// validates the given fee. without a fee we go with the minimum fee
+/// required by the feerefunder.
+pub fn ibc_fee_or_min(
+    deps: Deps<NeutronQuery>,
+    ibc_fee: Option<IbcFee>,
+) -> Result<IbcFee, IbcFeeError> {
+    match ibc_fee {
+        Some(ibc_fee) => validate_ibc_fee(deps, ibc_fee),
+        None => Ok(query_min_ibc_fee(deps)?),
+    }
+}
+
+fn covers_fee(fee: &[Coin], min_fee: &[Coin]) -> bool {
+    min_fee.iter().all(|min| {
+        fee.iter()
+            .any(|coin| coin.denom == min.denom && coin.amount >= min.amount)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::marker::PhantomData;
+
+    use cosmwasm_std::{
+        coin, coins,
+        testing::{MockApi, MockQuerier, MockStorage},
+        to_binary, ContractResult, OwnedDeps, SystemResult,
+    };
+
+    use super::*;
+
+    fn min_fee() -> IbcFee {
+        IbcFee {
+            recv_fee: vec![],
+            ack_fee: coins(1000, "untrn"),
+            timeout_fee: coins(1000, "untrn"),
+        }
+    }
+
+    fn mock_deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier<NeutronQuery>, NeutronQuery> {
+        let querier =
+            MockQuerier::<NeutronQuery>::new(&[]).with_custom_handler(|query| match query {
+                NeutronQuery::MinIbcFee {} => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&MinIbcFeeResponse { min_fee: min_fee() }).unwrap(),
+                )),
+                _ => panic!("unexpected neutron query: {query:?}"),
+            });
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier,
+            custom_query_type: PhantomData,
+        }
+    }
+
+    #[test]
+    fn accepts_fees_covering_the_minimum() {
+        let deps = mock_deps();
+
+        assert_eq!(
+            validate_ibc_fee(deps.as_ref(), min_fee()).unwrap(),
+            min_fee()
+        );
+
+        // higher fees and fees in additional denoms are fine
+        let fee = IbcFee {
+            recv_fee: vec![],
+            ack_fee: vec![coin(2000, "untrn"), coin(1, "uatom")],
+            timeout_fee: coins(1500, "untrn"),
+        };
+        assert_eq!(validate_ibc_fee(deps.as_ref(), fee.clone()).unwrap(), fee);
+    }
+
+    #[test]
+    fn rejects_fees_the_feerefunder_rejects() {
+        let deps = mock_deps();
+
+        let invalid_fees = [
+            IbcFee {
+                recv_fee: coins(1, "untrn"),
+                ..min_fee()
+            },
+            IbcFee {
+                ack_fee: coins(999, "untrn"),
+                ..min_fee()
+            },
+            IbcFee {
+                timeout_fee: coins(1000, "uatom"),
+                ..min_fee()
+            },
+            IbcFee {
+                timeout_fee: vec![],
+                ..min_fee()
+            },
+        ];
+        for fee in invalid_fees {
+            let err = validate_ibc_fee(deps.as_ref(), fee).unwrap_err();
+            assert!(matches!(err, IbcFeeError::InvalidIbcFee {}));
+        }
+    }
+
+    #[test]
+    fn defaults_to_the_minimum_fee() {
+        let deps = mock_deps();
+
+        assert_eq!(ibc_fee_or_min(deps.as_ref(), None).unwrap(), min_fee());
+        ibc_fee_or_min(
+            deps.as_ref(),
+            Some(IbcFee {
+                ack_fee: vec![],
+                ..min_fee()
+            }),
+        )
+        .unwrap_err();
+    }
+}
@@ -0,0 +1,52 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Env, StdResult, Storage, Timestamp, Uint64};
+use cw_storage_plus::Item;
+
+/// time of the last tick that paid a tip
+pub const LAST_PAID_TICK: Item<Timestamp> = Item::new("last_paid_tick");
+
+/// keeper tip of a contract. contracts tip in their own `T`, e.g. a
+/// fixed amount or a share of the ticked balances.
+#[cw_serde]
+pub struct KeeperTipResponse<T> {
+    pub keeper_tip: Option<T>,
+    /// time of the last tick that paid a tip
+    pub last_paid_tick: Option<Timestamp>,
+}
+
+/// whether at least `min_interval` seconds passed since the last paid tick
+pub fn is_tip_due(storage: &dyn Storage, env: &Env, min_interval: Uint64) -> StdResult<bool> {
+    Ok(match LAST_PAID_TICK.may_load(storage)? {
+        Some(last_paid_tick) => env.block.time >= last_paid_tick.plus_seconds(min_interval.u64()),
+        None => true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_env, MockStorage};
+
+    use super::*;
+
+    #[test]
+    fn tip_is_due_without_a_paid_tick() {
+        let storage = MockStorage::new();
+
+        assert!(is_tip_due(&storage, &mock_env(), Uint64::new(60)).unwrap());
+    }
+
+    #[test]
+    fn tip_is_due_once_the_interval_passed() {
+        let mut storage = MockStorage::new();
+        let mut env = mock_env();
+        LAST_PAID_TICK.save(&mut storage, &env.block.time).unwrap();
+
+        assert!(!is_tip_due(&storage, &env, Uint64::new(60)).unwrap());
+
+        env.block.time = env.block.time.plus_seconds(59);
+        assert!(!is_tip_due(&storage, &env, Uint64::new(60)).unwrap());
+
+        env.block.time = env.block.time.plus_seconds(1);
+        assert!(is_tip_due(&storage, &env, Uint64::new(60)).unwrap());
+    }
+}
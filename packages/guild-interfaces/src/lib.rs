@@ -0,0 +1,5 @@
+pub mod admin;
+pub mod cron;
+pub mod deposit;
+pub mod fee;
+pub mod keeper;
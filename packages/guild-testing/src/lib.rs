@@ -0,0 +1,289 @@
+//! neutron modules mocked for the multi-test suites of the contracts.
+//! IBC transfers, ICA and ICQ registrations succeed right away, their
+//! acknowledgements and callbacks are sent by the tests.
+
+use anyhow::{bail, Result as AnyResult};
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    testing::MockApi, to_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CosmosMsg,
+    CustomQuery, Empty, MemoryStorage, Querier, Storage,
+};
+use cw_multi_test::{
+    App, AppResponse, BankKeeper, BasicAppBuilder, CosmosRouter, Module, WasmKeeper,
+};
+use cw_storage_plus::Item;
+use neutron_sdk::{
+    bindings::{
+        msg::{
+            IbcFee, MsgIbcTransferResponse, MsgRegisterInterchainQueryResponse,
+            MsgSubmitTxResponse, NeutronMsg,
+        },
+        query::{
+            MinIbcFeeResponse, NeutronQuery, QueryRegisteredQueryResponse,
+            QueryRegisteredQueryResultResponse,
+        },
+        types::{Height, InterchainQueryResult, RegisteredQuery, StorageValue},
+    },
+    interchain_queries::{
+        types::QueryType,
+        v045::{
+            helpers::{create_account_denom_balance_key, decode_and_convert},
+            types::BANK_STORE_KEY,
+        },
+    },
+};
+use prost::Message;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+/// holds the funds and fees of IBC transfers until they are acknowledged
+pub const IBC_ESCROW: &str = "ibc_escrow";
+
+/// IBC transfers sent through the mock, in the order they were sent
+const IBC_TRANSFERS: Item<Vec<SentTransfer>> = Item::new("mock_ibc_transfers");
+/// ICA balances reported by the mocked interchain query, along with the
+/// local height they were submitted at
+const ICQ_BALANCES: Item<(u64, Vec<Coin>)> = Item::new("mock_icq_balances");
+/// sequence of the last ICA tx submitted through the mock
+const LAST_SEQUENCE: Item<u64> = Item::new("mock_last_sequence");
+
+pub type NeutronApp =
+    App<BankKeeper, MockApi, MemoryStorage, NeutronMock, WasmKeeper<NeutronMsg, NeutronQuery>>;
+
+#[cw_serde]
+pub struct SentTransfer {
+    pub sequence: u64,
+    pub channel: String,
+    pub receiver: String,
+    pub token: Coin,
+    pub memo: String,
+}
+
+/// stands in for the neutron transfer, feerefunder, interchain txs and
+/// interchain queries modules
+pub struct NeutronMock {
+    /// fee returned by the `MinIbcFee` query
+    pub min_ibc_fee: IbcFee,
+    /// connection of the interchain account and queries
+    pub connection_id: String,
+    /// channel the ICA txs are sent over
+    pub ica_channel_id: String,
+    /// address of the interchain account on the remote chain
+    pub ica_address: String,
+    /// id given to registered interchain queries
+    pub query_id: u64,
+}
+
+impl NeutronMock {
+    /// mock for contracts that only send IBC transfers
+    pub fn new(min_ibc_fee: IbcFee) -> Self {
+        NeutronMock {
+            min_ibc_fee,
+            connection_id: "connection-0".to_string(),
+            ica_channel_id: "channel-10".to_string(),
+            ica_address: "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5z5tpwxqergd3c8g7rusqqlvp8l"
+                .to_string(),
+            query_id: 1,
+        }
+    }
+}
+
+impl Module for NeutronMock {
+    type ExecT = NeutronMsg;
+    type QueryT = NeutronQuery;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: NeutronMsg,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match msg {
+            NeutronMsg::IbcTransfer {
+                source_channel,
+                token,
+                receiver,
+                memo,
+                fee,
+                ..
+            } => {
+                let mut escrowed: Vec<Coin> = vec![];
+                for coin in [&token]
+                    .into_iter()
+                    .chain(&fee.ack_fee)
+                    .chain(&fee.timeout_fee)
+                {
+                    match escrowed.iter_mut().find(|c| c.denom == coin.denom) {
+                        Some(c) => c.amount += coin.amount,
+                        None => escrowed.push(coin.clone()),
+                    }
+                }
+                router.execute(
+                    api,
+                    storage,
+                    block,
+                    sender,
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: IBC_ESCROW.to_string(),
+                        amount: escrowed,
+                    }),
+                )?;
+
+                let mut transfers = IBC_TRANSFERS.may_load(storage)?.unwrap_or_default();
+                let sequence = transfers.len() as u64 + 1;
+                transfers.push(SentTransfer {
+                    sequence,
+                    channel: source_channel.to_string(),
+                    receiver,
+                    token,
+                    memo,
+                });
+                IBC_TRANSFERS.save(storage, &transfers)?;
+
+                Ok(AppResponse {
+                    events: vec![],
+                    data: Some(to_binary(&MsgIbcTransferResponse {
+                        sequence_id: sequence,
+                        channel: source_channel,
+                    })?),
+                })
+            }
+            NeutronMsg::RegisterInterchainAccount { .. } => Ok(AppResponse::default()),
+            NeutronMsg::RegisterInterchainQuery { .. } => Ok(AppResponse {
+                events: vec![],
+                data: Some(to_binary(&MsgRegisterInterchainQueryResponse {
+                    id: self.query_id,
+                })?),
+            }),
+            NeutronMsg::SubmitTx { .. } => {
+                let sequence = LAST_SEQUENCE.may_load(storage)?.unwrap_or_default() + 1;
+                LAST_SEQUENCE.save(storage, &sequence)?;
+                Ok(AppResponse {
+                    events: vec![],
+                    data: Some(to_binary(&MsgSubmitTxResponse {
+                        sequence_id: sequence,
+                        channel: self.ica_channel_id.to_string(),
+                    })?),
+                })
+            }
+            msg => bail!("unexpected neutron msg: {msg:?}"),
+        }
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Empty,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        bail!("sudo is not supported by the neutron mock")
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: NeutronQuery,
+    ) -> AnyResult<Binary> {
+        match request {
+            NeutronQuery::MinIbcFee {} => Ok(to_binary(&MinIbcFeeResponse {
+                min_fee: self.min_ibc_fee.clone(),
+            })?),
+            NeutronQuery::RegisteredInterchainQuery { query_id } => {
+                let (height, _) = ICQ_BALANCES.load(storage)?;
+                Ok(to_binary(&QueryRegisteredQueryResponse {
+                    registered_query: RegisteredQuery {
+                        id: query_id,
+                        owner: "forwarder".to_string(),
+                        query_type: QueryType::KV,
+                        keys: vec![],
+                        transactions_filter: "".to_string(),
+                        connection_id: self.connection_id.to_string(),
+                        update_period: 5,
+                        last_submitted_result_local_height: height,
+                        last_submitted_result_remote_height: Height {
+                            revision_number: 0,
+                            revision_height: height,
+                        },
+                        deposit: vec![],
+                        submit_timeout: 0,
+                        registered_at_height: 0,
+                    },
+                })?)
+            }
+            NeutronQuery::InterchainQueryResult { .. } => {
+                let (height, balances) = ICQ_BALANCES.load(storage)?;
+                let ica_address = decode_and_convert(&self.ica_address)?;
+                let mut kv_results: Vec<StorageValue> = vec![];
+                for balance in balances {
+                    kv_results.push(StorageValue {
+                        storage_prefix: BANK_STORE_KEY.to_string(),
+                        key: Binary(create_account_denom_balance_key(
+                            &ica_address,
+                            &balance.denom,
+                        )?),
+                        value: Binary(
+                            ProtoCoin {
+                                denom: balance.denom,
+                                amount: balance.amount.to_string(),
+                            }
+                            .encode_to_vec(),
+                        ),
+                    });
+                }
+                Ok(to_binary(&QueryRegisteredQueryResultResponse {
+                    result: InterchainQueryResult {
+                        kv_results,
+                        height,
+                        revision: 0,
+                    },
+                })?)
+            }
+            request => bail!("unexpected neutron query: {request:?}"),
+        }
+    }
+}
+
+/// app with the mocked neutron modules
+pub fn neutron_app(mock: NeutronMock) -> NeutronApp {
+    BasicAppBuilder::<NeutronMsg, NeutronQuery>::new_custom()
+        .with_custom(mock)
+        .build(|_, _, _| {})
+}
+
+/// IBC transfers sent so far, in the order they were sent
+pub fn ibc_transfers(app: &NeutronApp) -> Vec<SentTransfer> {
+    app.read_module(|_, _, storage| IBC_TRANSFERS.may_load(storage))
+        .unwrap()
+        .unwrap_or_default()
+}
+
+/// sequence of the last submitted ICA tx
+pub fn last_ica_sequence(app: &NeutronApp) -> u64 {
+    app.read_module(|_, _, storage| LAST_SEQUENCE.load(storage))
+        .unwrap()
+}
+
+/// submits a new result of the ICA balance query
+pub fn set_icq_balances(app: &mut NeutronApp, height: u64, balances: Vec<Coin>) {
+    app.init_modules(|_, _, storage| ICQ_BALANCES.save(storage, &(height, balances)))
+        .unwrap()
+}